@@ -0,0 +1,69 @@
+//! Timings for building, composing and running morphisms.
+//!
+//! Run with `cargo bench --bench morphism`, and again with
+//! `--no-default-features` to compare the two backends.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use coyoneda::Coyoneda;
+use coyoneda::functor::Covariant;
+use coyoneda::morphism::Morphism;
+
+// Call `f` for about a tenth of a second, and print the mean time per
+// call.
+fn bench<R, F: FnMut() -> R>(name: &str, mut f: F) {
+    let budget = Duration::from_millis(100);
+    let start = Instant::now();
+    let mut iters = 0u32;
+    while start.elapsed() < budget {
+        for _ in 0..64 {
+            black_box(f());
+        }
+        iters += 64;
+    }
+    let ns = start.elapsed().as_nanos() / u128::from(iters);
+    println!("{:<32} {:>10} ns", name, ns);
+}
+
+fn chain(n: usize) -> Morphism<'static, u64> {
+    let mut f = Morphism::new::<u64>();
+    for i in 0..n as u64 {
+        f = f.tail(move |x| x.wrapping_mul(31).wrapping_add(i));
+    }
+    f
+}
+
+// The same steps as `chain(n)`, with the first `threshold` of them fused
+// into one.
+fn fused(n: usize, threshold: usize) -> Morphism<'static, u64> {
+    let mut f = Morphism::new::<u64>();
+    f.fuse_below(threshold);
+    for i in 0..n as u64 {
+        f = f.tail(move |x| x.wrapping_mul(31).wrapping_add(i));
+    }
+    f
+}
+
+fn main() {
+    for &n in &[1, 4, 8, 32] {
+        bench(&format!("build/{}", n), || chain(black_box(n)));
+        let f = chain(n);
+        bench(&format!("run/{}", n), || f.run(black_box(7)));
+        bench(&format!("build_fused/{}", n), || fused(black_box(n), 16));
+        let f = fused(n, 16);
+        bench(&format!("run_fused/{}", n), || f.run(black_box(7)));
+        let (g, h) = (chain(n / 2), chain(n - n / 2));
+        bench(&format!("then/{}", n), || g.clone().then(h.clone()));
+        bench(&format!("then_front/{}", n), || h.clone().then_front(g.clone()));
+        let gh = g.clone().then(h.clone());
+        bench(&format!("run_then/{}", n), || gh.run(black_box(7)));
+        bench(&format!("coyoneda/{}", n), || {
+            let mut c = Coyoneda::from(Some(black_box(7u64)));
+            for i in 0..n as u64 {
+                c = c.fmap(move |x| x.wrapping_mul(31).wrapping_add(i));
+            }
+            c.unwrap()
+        });
+    }
+}
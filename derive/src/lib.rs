@@ -0,0 +1,317 @@
+//! Derive macros for the `coyoneda` crate, re-exported from it with the
+//! `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, GenericArgument, GenericParam,
+    Ident, Path, PathArguments, Result, Token, Type,
+};
+
+/// Derive `Param`, `ReParam`, `Covariant` and `gat::Functor` for a
+/// struct or enum, mapping over its last type parameter.
+///
+/// Fields of the mapped type are passed to the function, fields that
+/// don't mention it are moved as they are, and tuples are mapped
+/// element by element. Any other field type that mentions the mapped
+/// parameter must itself implement `gat::Functor` over its last type
+/// parameter, which includes the type being derived for:
+///
+/// ```rust
+/// use coyoneda::Functor;
+///
+/// #[derive(Functor)]
+/// enum Tree<A> {
+///     Leaf(A),
+///     Node(Vec<Tree<A>>),
+/// }
+/// ```
+#[proc_macro_derive(Functor)]
+pub fn derive_functor(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    functor(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn functor(input: DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let param = match input.generics.type_params().last() {
+        Some(param) if param.bounds.is_empty() => param.ident.clone(),
+        Some(param) => return Err(Error::new_spanned(param, "the mapped type parameter can't have bounds")),
+        None => return Err(Error::new_spanned(&input.ident, "expected a type parameter to map over")),
+    };
+    if let Some(clause) = &input.generics.where_clause {
+        if mentions(&quote!(#clause), &param) {
+            return Err(Error::new_spanned(clause, "the mapped type parameter can't have bounds"));
+        }
+    }
+
+    let b = Ident::new("__B", Span::call_site());
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let rebound = rebind(&input, &param, &b);
+
+    // The mapping runs in a nested function that takes the closure as a
+    // trait object, so that recursive types don't instantiate it with a
+    // new closure type at every level.
+    let mut go_generics = input.generics.clone();
+    go_generics.params.push(parse_quote!(#b));
+    let (go_generics, _, _) = go_generics.split_for_impl();
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (pat, expr) = fields(quote!(#name), &data.fields, &param)?;
+            quote!(match this { #pat => #expr })
+        }
+        Data::Enum(data) => {
+            let arms = data.variants.iter().map(|v| {
+                let ident = &v.ident;
+                let (pat, expr) = fields(quote!(#name::#ident), &v.fields, &param)?;
+                Ok(quote!(#pat => #expr,))
+            }).collect::<Result<Vec<_>>>()?;
+            quote!(match this { #(#arms)* })
+        }
+        Data::Union(_) => return Err(Error::new_spanned(name, "unions can't be mapped over")),
+    };
+
+    let mut covariant_generics = input.generics.clone();
+    covariant_generics.params.insert(0, parse_quote!('__a));
+    covariant_generics.params.push(parse_quote!(#b));
+    let (covariant_generics, _, _) = covariant_generics.split_for_impl();
+    let mut reparam_generics = input.generics.clone();
+    reparam_generics.params.push(parse_quote!(#b));
+    let (reparam_generics, _, _) = reparam_generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::coyoneda::functor::gat::Functor for #name #ty_generics #where_clause {
+            type Param = #param;
+            type Rebind<#b> = #rebound;
+
+            fn fmap<#b, __F: FnMut(#param) -> #b>(self, mut __f: __F) -> #rebound {
+                #[allow(unused_variables, clippy::redundant_closure)]
+                fn go #go_generics (this: #name #ty_generics, __f: &mut dyn FnMut(#param) -> #b) -> #rebound
+                    #where_clause
+                {
+                    #body
+                }
+                go(self, &mut __f)
+            }
+        }
+
+        impl #impl_generics ::coyoneda::functor::parametric::Param for #name #ty_generics #where_clause {
+            type Param = #param;
+        }
+
+        impl #reparam_generics ::coyoneda::functor::parametric::ReParam<#b> for #name #ty_generics #where_clause {
+            type Output = #rebound;
+        }
+
+        impl #covariant_generics ::coyoneda::functor::Covariant<'__a, #b> for #name #ty_generics #where_clause {
+            fn fmap<__F: '__a + Fn(#param) -> #b>(self, f: __F) -> #rebound {
+                <Self as ::coyoneda::functor::gat::Functor>::fmap(self, f)
+            }
+        }
+    })
+}
+
+/// Derive `NaturalTransform` into each type named by a
+/// `#[natural_transform(...)]` attribute.
+///
+/// With `#[natural_transform(Target<A>)]`, the target must have the
+/// same shape: every variant, or the struct itself, is rebuilt under
+/// the same name, with each field converted by `Into`. With
+/// `#[natural_transform(into = Target<A>)]`, the whole value is
+/// converted by an existing `From` impl instead.
+///
+/// ```rust
+/// use coyoneda::{Functor, NaturalTransform};
+///
+/// #[derive(Functor)]
+/// enum Console<A> { Print(String, A), Exit }
+///
+/// #[derive(Functor, NaturalTransform)]
+/// #[natural_transform(Console<A>)]
+/// enum Io<A> { Print(&'static str, A), Exit }
+/// ```
+#[proc_macro_derive(NaturalTransform, attributes(natural_transform))]
+pub fn derive_natural_transform(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    natural_transform(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+enum Target {
+    Fields(Type),
+    Into(Type),
+}
+
+impl Parse for Target {
+    fn parse(input: ParseStream) -> Result<Target> {
+        let fork = input.fork();
+        if fork.parse::<Ident>().is_ok_and(|ident| ident == "into") && fork.peek(Token![=]) {
+            input.parse::<Ident>()?;
+            input.parse::<Token![=]>()?;
+            return Ok(Target::Into(input.parse()?));
+        }
+        Ok(Target::Fields(input.parse()?))
+    }
+}
+
+fn natural_transform(input: DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let targets = input.attrs.iter()
+        .filter(|attr| attr.path().is_ident("natural_transform"))
+        .map(|attr| attr.parse_args::<Target>())
+        .collect::<Result<Vec<_>>>()?;
+    if targets.is_empty() {
+        return Err(Error::new_spanned(name, "expected a #[natural_transform(Target)] attribute"));
+    }
+    let impls = targets.into_iter().map(|target| {
+        let (ty, body) = match target {
+            Target::Into(ty) => (ty, quote!(::core::convert::From::from(self))),
+            Target::Fields(ty) => {
+                let path = constructor(&ty)?;
+                let body = match &input.data {
+                    Data::Struct(data) => {
+                        let (pat, expr) = convert(quote!(#name), quote!(#path), &data.fields);
+                        quote!(match self { #pat => #expr })
+                    }
+                    Data::Enum(data) => {
+                        let arms = data.variants.iter().map(|v| {
+                            let ident = &v.ident;
+                            let (pat, expr) = convert(quote!(#name::#ident), quote!(#path::#ident), &v.fields);
+                            quote!(#pat => #expr,)
+                        });
+                        quote!(match self { #(#arms)* })
+                    }
+                    Data::Union(_) => return Err(Error::new_spanned(name, "unions can't be transformed")),
+                };
+                (ty, body)
+            }
+        };
+        Ok(quote! {
+            impl #impl_generics ::coyoneda::functor::NaturalTransform<#ty> for #name #ty_generics #where_clause {
+                fn transform(self) -> #ty {
+                    #body
+                }
+            }
+        })
+    }).collect::<Result<Vec<_>>>()?;
+    Ok(quote!(#(#impls)*))
+}
+
+// The path of a type, with its generic arguments turned into a
+// turbofish, so that it can be used in expressions.
+fn constructor(ty: &Type) -> Result<Path> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let mut path = path.path.clone();
+            for segment in &mut path.segments {
+                if let PathArguments::AngleBracketed(args) = &mut segment.arguments {
+                    args.colon2_token = Some(Default::default());
+                }
+            }
+            Ok(path)
+        }
+        _ => Err(Error::new_spanned(ty, "expected the path of a struct or enum")),
+    }
+}
+
+// A pattern binding every field of a struct or variant, and an
+// expression building its counterpart from the converted fields.
+fn convert(from: TokenStream2, to: TokenStream2, fields: &Fields) -> (TokenStream2, TokenStream2) {
+    let names: Vec<Ident> = (0..fields.len()).map(|i| format_ident!("__field{}", i)).collect();
+    match fields {
+        Fields::Named(named) => {
+            let idents: Vec<_> = named.named.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+            (quote!(#from { #(#idents: #names),* }),
+             quote!(#to { #(#idents: ::core::convert::Into::into(#names)),* }))
+        }
+        Fields::Unnamed(_) => {
+            (quote!(#from(#(#names),*)), quote!(#to(#(::core::convert::Into::into(#names)),*)))
+        }
+        Fields::Unit => (quote!(#from), quote!(#to)),
+    }
+}
+
+// The type being derived for, with the mapped parameter replaced by `b`.
+fn rebind(input: &DeriveInput, param: &Ident, b: &Ident) -> TokenStream2 {
+    let name = &input.ident;
+    let args = input.generics.params.iter().map(|p| match p {
+        GenericParam::Type(t) if t.ident == *param => quote!(#b),
+        GenericParam::Type(t) => {
+            let ident = &t.ident;
+            quote!(#ident)
+        }
+        GenericParam::Lifetime(l) => {
+            let lifetime = &l.lifetime;
+            quote!(#lifetime)
+        }
+        GenericParam::Const(c) => {
+            let ident = &c.ident;
+            quote!(#ident)
+        }
+    });
+    quote!(#name<#(#args),*>)
+}
+
+// A pattern binding every field of a struct or variant, and an
+// expression rebuilding it from the mapped fields.
+fn fields(path: TokenStream2, fields: &Fields, param: &Ident) -> Result<(TokenStream2, TokenStream2)> {
+    let names: Vec<Ident> = (0..fields.len()).map(|i| format_ident!("__field{}", i)).collect();
+    let mapped = fields.iter().zip(&names)
+        .map(|(field, name)| map(&field.ty, quote!(#name), param))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(match fields {
+        Fields::Named(named) => {
+            let idents: Vec<_> = named.named.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+            (quote!(#path { #(#idents: #names),* }), quote!(#path { #(#idents: #mapped),* }))
+        }
+        Fields::Unnamed(_) => (quote!(#path(#(#names),*)), quote!(#path(#(#mapped),*))),
+        Fields::Unit => (quote!(#path), quote!(#path)),
+    })
+}
+
+// An expression mapping `expr`, of type `ty`, with `__f`.
+fn map(ty: &Type, expr: TokenStream2, param: &Ident) -> Result<TokenStream2> {
+    if !mentions(&quote!(#ty), param) {
+        return Ok(expr);
+    }
+    match ty {
+        Type::Paren(paren) => map(&paren.elem, expr, param),
+        Type::Group(group) => map(&group.elem, expr, param),
+        Type::Path(path) if path.qself.is_none() && path.path.is_ident(param) => Ok(quote!(__f(#expr))),
+        Type::Tuple(tuple) => {
+            let names: Vec<Ident> = (0..tuple.elems.len()).map(|i| format_ident!("__elem{}", i)).collect();
+            let mapped = tuple.elems.iter().zip(&names)
+                .map(|(ty, name)| map(ty, quote!(#name), param))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(quote!({ let (#(#names,)*) = #expr; (#(#mapped,)*) }))
+        }
+        Type::Path(path) if path.qself.is_none() => {
+            let last = path.path.segments.last().unwrap();
+            let args = match &last.arguments {
+                PathArguments::AngleBracketed(args) => &args.args,
+                _ => return Err(Error::new_spanned(ty, "can't map over this field")),
+            };
+            let types: Vec<&Type> = args.iter().filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            }).collect();
+            let (inner, rest) = types.split_last().unwrap();
+            if rest.iter().any(|ty| mentions(&quote!(#ty), param)) || !mentions(&quote!(#inner), param) {
+                return Err(Error::new_spanned(ty, "the mapped type parameter may only appear in the last type argument of a field"));
+            }
+            let inner = map(inner, quote!(__x), param)?;
+            Ok(quote!(::coyoneda::functor::gat::Functor::fmap(#expr, |__x| #inner)))
+        }
+        _ => Err(Error::new_spanned(ty, "can't map over this field")),
+    }
+}
+
+fn mentions(tokens: &TokenStream2, param: &Ident) -> bool {
+    tokens.clone().into_iter().any(|tt| match tt {
+        TokenTree::Ident(ident) => ident == *param,
+        TokenTree::Group(group) => mentions(&group.stream(), param),
+        _ => false,
+    })
+}
@@ -0,0 +1,80 @@
+//! Suspended mapping over both parameters of a bifunctor.
+//!
+//! `BiCoyoneda` accumulates one chain per parameter, so that for
+//! example the error channel of a `Result` can be mapped alongside the
+//! success channel, with both applied in a single `bimap` when lowered.
+
+use crate::morphism::{Morphism, Outlives};
+use crate::functor::Bifunctor;
+use crate::functor::parametric::{BiParam, BiReParam};
+
+pub struct BiCoyoneda<'a, T: BiParam, C, D> {
+    point: T,
+    first: Morphism<'a, T::First, C>,
+    second: Morphism<'a, T::Second, D>
+}
+
+impl<'a, T: 'a + BiParam, C: 'a, D: 'a> BiCoyoneda<'a, T, C, D> {
+
+    pub fn map_first<E: Outlives<'a>, F: Fn(C) -> E + 'a>(self, f: F) -> BiCoyoneda<'a, T, E, D> {
+        BiCoyoneda{point: self.point, first: self.first.tail(f), second: self.second}
+    }
+
+    pub fn map_second<E: Outlives<'a>, F: Fn(D) -> E + 'a>(self, f: F) -> BiCoyoneda<'a, T, C, E> {
+        BiCoyoneda{point: self.point, first: self.first, second: self.second.tail(f)}
+    }
+
+    pub fn bimap<X: Outlives<'a>, Y: Outlives<'a>, F, G>(self, f: F, g: G) -> BiCoyoneda<'a, T, X, Y>
+        where F: Fn(C) -> X + 'a, G: Fn(D) -> Y + 'a {
+        BiCoyoneda{point: self.point, first: self.first.tail(f), second: self.second.tail(g)}
+    }
+
+    pub fn unwrap(self) -> <T as BiReParam<C, D>>::Output
+        where T: Bifunctor<'a, C, D>, T::First: 'a, T::Second: 'a {
+        let (f, g) = (self.first, self.second);
+        self.point.bimap(move |a| f.run(a), move |b| g.run(b))
+    }
+
+}
+
+impl<'a, A: 'a, E: 'a, C: 'a, D: 'a> BiCoyoneda<'a, Result<A, E>, C, D> {
+
+    /// Suspend a mapping of the success channel of a `Result`.
+    pub fn map<X: Outlives<'a>, F: Fn(C) -> X + 'a>(self, f: F) -> BiCoyoneda<'a, Result<A, E>, X, D> {
+        self.map_first(f)
+    }
+
+    /// Suspend a mapping of the error channel of a `Result`.
+    pub fn map_err<X: Outlives<'a>, F: Fn(D) -> X + 'a>(self, f: F) -> BiCoyoneda<'a, Result<A, E>, C, X> {
+        self.map_second(f)
+    }
+
+}
+
+impl<'a, T: BiParam> From<T> for BiCoyoneda<'a, T, T::First, T::Second> {
+    fn from(x: T) -> BiCoyoneda<'a, T, T::First, T::Second> {
+        BiCoyoneda{point: x, first: Morphism::new(), second: Morphism::new()}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BiCoyoneda;
+
+    #[test]
+    fn map_and_map_err() {
+        let x: Result<i32, &str> = Err("nope");
+        let y = BiCoyoneda::from(x)
+            .map(|n| n + 1)
+            .map_err(|e| e.len())
+            .map(|n| n.to_string())
+            .map_err(|n| n * 2);
+        assert_eq!(y.unwrap(), Err(8))
+    }
+
+    #[test]
+    fn bimap_pair() {
+        let y = BiCoyoneda::from((1, "two")).bimap(|n| n + 1, |s| s.len());
+        assert_eq!(y.unwrap(), (2, 3))
+    }
+}
@@ -0,0 +1,67 @@
+//! A `Coyoneda` over a borrowed point.
+//!
+//! `CoyonedaRef` captures a `&T` instead of a `T`, so a large structure
+//! can be mapped over lazily and lowered into a new container, while the
+//! original stays available to the caller.
+
+use crate::morphism::{Morphism, Outlives};
+use crate::functor::{Covariant, CovariantRef};
+use crate::functor::parametric::{Param, ReParam};
+
+pub struct CoyonedaRef<'a, T: Param, B> where T::Param: 'a {
+    point: &'a T,
+    morph: Morphism<'a, &'a T::Param, B>
+}
+
+impl<'a, T: 'a + Param, B: 'a> CoyonedaRef<'a, T, B> where T::Param: 'a {
+
+    pub fn unwrap(self) -> <T as ReParam<B>>::Output
+        where T: CovariantRef<'a, B> {
+        let m = self.morph;
+        self.point.fmap_ref(move |a| { m.run(a) })
+    }
+
+}
+
+impl<'a, T: 'a + Param, B> Param for CoyonedaRef<'a, T, B> where T::Param: 'a {
+    type Param = B;
+}
+
+impl<'a, T: 'a + Param, B, C> ReParam<C> for CoyonedaRef<'a, T, B> where T::Param: 'a {
+    type Output = CoyonedaRef<'a, T, C>;
+}
+
+impl<'a, T: 'a + Param, B: Outlives<'a>, C: Outlives<'a>> Covariant<'a, C> for CoyonedaRef<'a, T, B> where T::Param: 'a {
+    fn fmap<F: Fn(B) -> C + 'a>(self, f: F) -> CoyonedaRef<'a, T, C> {
+        CoyonedaRef{point: self.point, morph: self.morph.tail(f)}
+    }
+}
+
+impl<'a, T: 'a + Param> From<&'a T> for CoyonedaRef<'a, T, &'a T::Param> where T::Param: 'a {
+    fn from(x: &'a T) -> CoyonedaRef<'a, T, &'a T::Param> {
+        CoyonedaRef{point: x, morph: Morphism::new()}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CoyonedaRef;
+    use crate::functor::Covariant;
+
+    #[test]
+    fn map_without_consuming() {
+        let x = Some(vec![1, 2, 3]);
+        let y = CoyonedaRef::from(&x)
+            .fmap(|v: &Vec<i32>| v.len())
+            .fmap(|n| n * 2);
+        assert_eq!(y.unwrap(), Some(6));
+        assert_eq!(x, Some(vec![1, 2, 3]))
+    }
+
+    #[test]
+    fn map_result_by_ref() {
+        let x: Result<String, String> = Err("nope".to_string());
+        let y = CoyonedaRef::from(&x).fmap(|s: &String| s.len());
+        assert_eq!(y.unwrap(), Err("nope".to_string()))
+    }
+}
@@ -0,0 +1,126 @@
+//! A functor over channel receivers.
+//!
+//! `MappedReceiver` wraps a `std::sync::mpsc::Receiver`, so it can be
+//! lifted into `Coyoneda`. Lowering the suspension yields a receiver
+//! that applies the accumulated chain to each message as it is received.
+
+use std::sync::mpsc::{Receiver, RecvError, RecvTimeoutError, TryRecvError};
+use std::time::Duration;
+
+use crate::functor::Covariant;
+use crate::functor::parametric::{Param, ReParam};
+
+trait Source<A> {
+    fn recv(&self) -> Result<A, RecvError>;
+    fn try_recv(&self) -> Result<A, TryRecvError>;
+    fn recv_timeout(&self, timeout: Duration) -> Result<A, RecvTimeoutError>;
+}
+
+impl<A> Source<A> for Receiver<A> {
+    fn recv(&self) -> Result<A, RecvError> {
+        Receiver::recv(self)
+    }
+
+    fn try_recv(&self) -> Result<A, TryRecvError> {
+        Receiver::try_recv(self)
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> Result<A, RecvTimeoutError> {
+        Receiver::recv_timeout(self, timeout)
+    }
+}
+
+struct Map<'a, A, F> {
+    inner: Box<dyn Source<A> + 'a>,
+    f: F
+}
+
+impl<'a, A, B, F: Fn(A) -> B> Source<B> for Map<'a, A, F> {
+    fn recv(&self) -> Result<B, RecvError> {
+        self.inner.recv().map(&self.f)
+    }
+
+    fn try_recv(&self) -> Result<B, TryRecvError> {
+        self.inner.try_recv().map(&self.f)
+    }
+
+    fn recv_timeout(&self, timeout: Duration) -> Result<B, RecvTimeoutError> {
+        self.inner.recv_timeout(timeout).map(&self.f)
+    }
+}
+
+pub struct MappedReceiver<'a, A> {
+    inner: Box<dyn Source<A> + 'a>
+}
+
+impl<'a, A: 'a> MappedReceiver<'a, A> {
+    pub fn new(receiver: Receiver<A>) -> MappedReceiver<'a, A> {
+        MappedReceiver{inner: Box::new(receiver)}
+    }
+}
+
+impl<'a, A> MappedReceiver<'a, A> {
+    /// Block until a message arrives, and return it with the chain applied.
+    pub fn recv(&self) -> Result<A, RecvError> {
+        self.inner.recv()
+    }
+
+    pub fn try_recv(&self) -> Result<A, TryRecvError> {
+        self.inner.try_recv()
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<A, RecvTimeoutError> {
+        self.inner.recv_timeout(timeout)
+    }
+}
+
+impl<'a, A> Iterator for MappedReceiver<'a, A> {
+    type Item = A;
+
+    /// Blocks like `Receiver::iter`, ending once the channel hangs up.
+    fn next(&mut self) -> Option<A> {
+        self.recv().ok()
+    }
+}
+
+impl<'a, A: 'a> From<Receiver<A>> for MappedReceiver<'a, A> {
+    fn from(receiver: Receiver<A>) -> MappedReceiver<'a, A> {
+        MappedReceiver::new(receiver)
+    }
+}
+
+impl<'a, A> Param for MappedReceiver<'a, A> {
+    type Param = A;
+}
+
+impl<'a, A, B> ReParam<B> for MappedReceiver<'a, A> {
+    type Output = MappedReceiver<'a, B>;
+}
+
+impl<'b: 'a, 'a, A: 'a, B> Covariant<'b, B> for MappedReceiver<'a, A> {
+    fn fmap<F: 'b + Fn(A) -> B>(self, f: F) -> MappedReceiver<'a, B> {
+        MappedReceiver{inner: Box::new(Map{inner: self.inner, f})}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{channel, TryRecvError};
+    use std::thread;
+
+    use super::MappedReceiver;
+    use crate::Coyoneda;
+    use crate::functor::Covariant;
+
+    #[test]
+    fn map_receiver() {
+        let (tx, rx) = channel();
+        let y = Coyoneda::from(MappedReceiver::new(rx))
+            .fmap(|n: i32| n * 2)
+            .fmap(|n| n.to_string())
+            .unwrap();
+        assert_eq!(y.try_recv(), Err(TryRecvError::Empty));
+        thread::spawn(move || for n in 1..4 { tx.send(n).unwrap() });
+        assert_eq!(y.collect::<Vec<_>>(), vec!["2", "4", "6"])
+    }
+}
@@ -0,0 +1,28 @@
+//! The part of the crate that is there whatever features are enabled:
+//! the functor traits, `Coyoneda` and `Morphism`.
+//!
+//! Everything else builds on these, and the larger part of it is
+//! behind features, so that crates can leave out what they don't need:
+//!
+//! * `std-collections`, on by default, has the instances for the types
+//!   in `std::collections`.
+//! * `sync`, on by default, has the instances for `Arc`, `Mutex` and
+//!   `RwLock`, along with `SharedCoyoneda` and the wrappers for channel
+//!   receivers and thread handles.
+//! * `unsafe-morphism`, on by default, moves values between the steps
+//!   of a `Morphism` through a raw slot. Without it, the crate has no
+//!   unsafe code, and the types along a morphism have to outlive it.
+//! * `futures` has the instances and wrappers for futures and streams.
+//! * `im`, `ndarray` and `smallvec` have the instances for types from
+//!   those crates.
+//!
+//! Instances for the types in the prelude are always available.
+
+pub use crate::Coyoneda;
+pub use crate::functor::{
+    Applicative, Bifunctor, Bivariant, Comonad, Contravariant, ContravariantOnce, Covariant,
+    CovariantOnce, CovariantRef, Extract, Foldable, Invariant, InvariantOnce, Monad,
+    NaturalTransform, Pure, Traversable,
+};
+pub use crate::functor::parametric::{BiParam, BiReParam, Param, ReParam};
+pub use crate::morphism::{Morphism, Outlives};
@@ -0,0 +1,137 @@
+//! Difference lists.
+//!
+//! A `DList` is a list represented by the chain of steps that build it
+//! up, so that adding elements at either end, and concatenating two
+//! lists, take constant time. The list is only put together when it is
+//! realized with `to_vec`.
+
+use std::iter::FromIterator;
+
+use crate::monoid::{Monoid, Semigroup};
+use crate::morphism::Morphism;
+
+/// A list of `A`, as a chain of steps that extend a `Vec`.
+///
+/// # Example
+///
+/// ```rust
+/// use coyoneda::dlist::DList;
+///
+/// let mut log = DList::singleton("start");
+/// log.push_back("work");
+/// log.push_front("init");
+/// let log = log.append(vec!["done"].into());
+/// assert_eq!(log.to_vec(), vec!["init", "start", "work", "done"]);
+/// ```
+pub struct DList<'a, A> {
+    // Steps append to the `Vec` built so far, so the list is the order
+    // in which they run.
+    morph: Morphism<'a, Vec<A>>,
+}
+
+impl<'a, A: Clone + 'a> DList<'a, A> {
+    /// The empty list.
+    pub fn new() -> DList<'a, A> {
+        DList{morph: Morphism::new()}
+    }
+
+    /// A list holding just `x`.
+    pub fn singleton(x: A) -> DList<'a, A> {
+        let mut xs = DList::new();
+        xs.push_back(x);
+        xs
+    }
+
+    /// Add `x` to the front of the list.
+    pub fn push_front(&mut self, x: A) {
+        self.morph.push_front(move |mut xs| { xs.push(x.clone()); xs })
+    }
+
+    /// Add `x` to the back of the list.
+    pub fn push_back(&mut self, x: A) {
+        self.morph.push_back(move |mut xs| { xs.push(x.clone()); xs })
+    }
+
+    /// Concatenate two lists.
+    pub fn append(self, other: DList<'a, A>) -> DList<'a, A> {
+        DList{morph: self.morph.then(other.morph)}
+    }
+
+    /// Put the list together.
+    pub fn to_vec(&self) -> Vec<A> {
+        self.morph.run(Vec::new())
+    }
+}
+
+impl<'a, A: Clone + 'a> Default for DList<'a, A> {
+    fn default() -> DList<'a, A> {
+        DList::new()
+    }
+}
+
+impl<'a, A> Clone for DList<'a, A> {
+    fn clone(&self) -> DList<'a, A> {
+        DList{morph: self.morph.clone()}
+    }
+}
+
+impl<'a, A: Clone + 'a> From<Vec<A>> for DList<'a, A> {
+    fn from(chunk: Vec<A>) -> DList<'a, A> {
+        DList{morph: Morphism::new().tail(move |mut xs: Vec<A>| { xs.extend_from_slice(&chunk); xs })}
+    }
+}
+
+impl<'a, A: Clone + 'a> FromIterator<A> for DList<'a, A> {
+    fn from_iter<I: IntoIterator<Item=A>>(iter: I) -> DList<'a, A> {
+        DList::from(iter.into_iter().collect::<Vec<A>>())
+    }
+}
+
+impl<'a, A: Clone + 'a> Extend<A> for DList<'a, A> {
+    fn extend<I: IntoIterator<Item=A>>(&mut self, iter: I) {
+        for x in iter {
+            self.push_back(x);
+        }
+    }
+}
+
+impl<'a, A: Clone + 'a> Semigroup for DList<'a, A> {
+    fn combine(self, other: DList<'a, A>) -> DList<'a, A> {
+        self.append(other)
+    }
+}
+
+impl<'a, A: Clone + 'a> Monoid for DList<'a, A> {
+    fn empty() -> DList<'a, A> {
+        DList::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DList;
+    use crate::monoid::Monoid;
+
+    #[test]
+    fn build_and_realize() {
+        let mut xs: DList<u32> = (3..6).collect();
+        xs.push_front(2);
+        xs.extend(vec![6, 7]);
+        let ys = DList::singleton(1).append(xs.clone());
+        assert_eq!(ys.to_vec(), (1..8).collect::<Vec<_>>());
+        assert_eq!(xs.to_vec(), (2..8).collect::<Vec<_>>());
+        assert_eq!(DList::concat(vec![ys.clone(), DList::empty(), ys]).to_vec().len(), 14);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn long_list() {
+        let mut xs = DList::new();
+        for i in 0..100000u64 {
+            if i % 2 == 0 { xs.push_back(i) } else { xs.push_front(i) }
+        }
+        let xs = xs.to_vec();
+        assert_eq!(xs.len(), 100000);
+        assert_eq!((xs[0], xs[99999]), (99999, 99998));
+    }
+}
@@ -0,0 +1,86 @@
+//! A sum type without the success/failure connotation of `Result`.
+
+use crate::functor::{Applicative, Bifunctor, Covariant, CovariantOnce, Monad, Pure, Traversable};
+use crate::functor::parametric::{BiParam, BiReParam, Param, ReParam};
+
+/// Either an `L` or an `R`. As a functor, `Either` maps over `Right`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R)
+}
+
+impl<L, R> Param for Either<L, R> {
+    type Param = R;
+}
+
+impl<L, R, B> ReParam<B> for Either<L, R> {
+    type Output = Either<L, B>;
+}
+
+impl<L, R> BiParam for Either<L, R> {
+    type First = L;
+    type Second = R;
+}
+
+impl<L, R, C, D> BiReParam<C, D> for Either<L, R> {
+    type Output = Either<C, D>;
+}
+
+impl<'a, L, R, B> Covariant<'a, B> for Either<L, R> {
+    fn fmap<F: 'a + Fn(R) -> B>(self, f: F) -> Self::Output {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, L, R, B> CovariantOnce<'a, B> for Either<L, R> {
+    fn fmap_once<F: FnOnce(R) -> B>(self, f: F) -> Self::Output {
+        match self {
+            Either::Left(l) => Either::Left(l),
+            Either::Right(r) => Either::Right(f(r))
+        }
+    }
+}
+
+impl<'a, L, R, B, C> Applicative<'a, B, C> for Either<L, R> {
+    fn map2<F: 'a + Fn(R, B) -> C>(self, other: Either<L, B>, f: F) -> Either<L, C> {
+        match (self, other) {
+            (Either::Right(r), Either::Right(b)) => Either::Right(f(r, b)),
+            (Either::Left(l), _) | (_, Either::Left(l)) => Either::Left(l)
+        }
+    }
+}
+
+impl<'a, L, R, B> Monad<'a, B> for Either<L, R> {
+    fn bind<F: 'a + Fn(R) -> Either<L, B>>(self, f: F) -> Either<L, B> {
+        match self {
+            Either::Left(l) => Either::Left(l),
+            Either::Right(r) => f(r)
+        }
+    }
+}
+
+impl<L, R> Pure for Either<L, R> {
+    fn pure(r: R) -> Either<L, R> {
+        Either::Right(r)
+    }
+}
+
+impl<'a, L, R, B> Traversable<'a, B> for Either<L, R> {
+    fn traverse<E, F: 'a + Fn(R) -> Result<B, E>>(self, f: F) -> Result<Either<L, B>, E> {
+        match self {
+            Either::Left(l) => Ok(Either::Left(l)),
+            Either::Right(r) => f(r).map(Either::Right)
+        }
+    }
+}
+
+impl<'a, L, R, C, D> Bifunctor<'a, C, D> for Either<L, R> {
+    fn bimap<F, G>(self, f: F, g: G) -> Either<C, D>
+        where F: 'a + Fn(L) -> C, G: 'a + Fn(R) -> D {
+        match self {
+            Either::Left(l) => Either::Left(f(l)),
+            Either::Right(r) => Either::Right(g(r))
+        }
+    }
+}
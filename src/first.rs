@@ -0,0 +1,75 @@
+//! Pairs that map over their first component.
+//!
+//! Tuples are functors in their last component. `First` wraps a pair so
+//! that a suspension can target the other side instead.
+
+use crate::functor::{Covariant, CovariantOnce, CovariantRef, Traversable};
+use crate::functor::parametric::{Param, ReParam};
+
+/// A pair `(A, X)` that, as a functor, maps over `A`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct First<A, X>(pub (A, X));
+
+impl<A, X> First<A, X> {
+    pub fn new(a: A, x: X) -> First<A, X> {
+        First((a, x))
+    }
+
+    pub fn into_inner(self) -> (A, X) {
+        self.0
+    }
+}
+
+impl<A, X> From<(A, X)> for First<A, X> {
+    fn from(pair: (A, X)) -> First<A, X> {
+        First(pair)
+    }
+}
+
+impl<A, X> Param for First<A, X> {
+    type Param = A;
+}
+
+impl<A, X, B> ReParam<B> for First<A, X> {
+    type Output = First<B, X>;
+}
+
+impl<'a, A, X, B> Covariant<'a, B> for First<A, X> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> First<B, X> {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, A, X, B> CovariantOnce<'a, B> for First<A, X> {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> First<B, X> {
+        let (a, x) = self.0;
+        First((f(a), x))
+    }
+}
+
+impl<'a, A, X: Clone, B> CovariantRef<'a, B> for First<A, X> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> First<B, X> {
+        First((f(&(self.0).0), (self.0).1.clone()))
+    }
+}
+
+impl<'a, A, X, B> Traversable<'a, B> for First<A, X> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<First<B, X>, E> {
+        let (a, x) = self.0;
+        f(a).map(|b| First((b, x)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::First;
+    use crate::Coyoneda;
+    use crate::functor::Covariant;
+
+    #[test]
+    fn fmap_first() {
+        let x = First::new(1, "one");
+        let y = Coyoneda::from(x).fmap(|n: i32| n + 1).fmap(|n| n.to_string()).unwrap();
+        assert_eq!(y.into_inner(), ("2".to_string(), "one"))
+    }
+}
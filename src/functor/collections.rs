@@ -0,0 +1,204 @@
+//! Instances for the collections in `std::collections`, available with
+//! the `std-collections` feature, which is on by default.
+
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::hash::{BuildHasher, Hash};
+
+use super::{Covariant, CovariantRef, Traversable};
+use super::parametric::{Param, ReParam};
+
+impl<A> Param for VecDeque<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for VecDeque<A> {
+    type Output = VecDeque<B>;
+}
+
+impl<A> Param for LinkedList<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for LinkedList<A> {
+    type Output = LinkedList<B>;
+}
+
+impl<K, V, S> Param for HashMap<K, V, S> {
+    type Param = V;
+}
+
+impl<K, V, B, S> ReParam<B> for HashMap<K, V, S> {
+    type Output = HashMap<K, B, S>;
+}
+
+impl<K, V> Param for BTreeMap<K, V> {
+    type Param = V;
+}
+
+impl<K, V, B> ReParam<B> for BTreeMap<K, V> {
+    type Output = BTreeMap<K, B>;
+}
+
+impl<A, S> Param for HashSet<A, S> {
+    type Param = A;
+}
+
+impl<A, B, S> ReParam<B> for HashSet<A, S> {
+    type Output = HashSet<B, S>;
+}
+
+impl<A> Param for BTreeSet<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for BTreeSet<A> {
+    type Output = BTreeSet<B>;
+}
+
+impl<A> Param for BinaryHeap<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for BinaryHeap<A> {
+    type Output = BinaryHeap<B>;
+}
+
+impl<'a, A, B> Covariant<'a, B> for VecDeque<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> VecDeque<B> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for VecDeque<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> VecDeque<B> {
+        self.iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B> Traversable<'a, B> for VecDeque<A> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<VecDeque<B>, E> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B> Covariant<'a, B> for LinkedList<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> LinkedList<B> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for LinkedList<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> LinkedList<B> {
+        self.iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B> Traversable<'a, B> for LinkedList<A> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<LinkedList<B>, E> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<'a, K: Eq + Hash, V, B, S> Covariant<'a, B> for HashMap<K, V, S>
+    where S: BuildHasher + Default {
+    fn fmap<F: 'a + Fn(V) -> B>(self, f: F) -> HashMap<K, B, S> {
+        self.into_iter().map(|(k, v)| (k, f(v))).collect()
+    }
+}
+
+impl<'a, K: Clone + Eq + Hash, V, B, S> CovariantRef<'a, B> for HashMap<K, V, S>
+    where S: BuildHasher + Default {
+    fn fmap_ref<F: 'a + Fn(&'a V) -> B>(&'a self, f: F) -> HashMap<K, B, S> {
+        self.iter().map(|(k, v)| (k.clone(), f(v))).collect()
+    }
+}
+
+impl<'a, K: Eq + Hash, V, B, S> Traversable<'a, B> for HashMap<K, V, S>
+    where S: BuildHasher + Default {
+    fn traverse<E, F: 'a + Fn(V) -> Result<B, E>>(self, f: F) -> Result<HashMap<K, B, S>, E> {
+        self.into_iter().map(|(k, v)| f(v).map(|b| (k, b))).collect()
+    }
+}
+
+impl<'a, K: Ord, V, B> Covariant<'a, B> for BTreeMap<K, V> {
+    fn fmap<F: 'a + Fn(V) -> B>(self, f: F) -> BTreeMap<K, B> {
+        self.into_iter().map(|(k, v)| (k, f(v))).collect()
+    }
+}
+
+impl<'a, K: Clone + Ord, V, B> CovariantRef<'a, B> for BTreeMap<K, V> {
+    fn fmap_ref<F: 'a + Fn(&'a V) -> B>(&'a self, f: F) -> BTreeMap<K, B> {
+        self.iter().map(|(k, v)| (k.clone(), f(v))).collect()
+    }
+}
+
+impl<'a, K: Ord, V, B> Traversable<'a, B> for BTreeMap<K, V> {
+    fn traverse<E, F: 'a + Fn(V) -> Result<B, E>>(self, f: F) -> Result<BTreeMap<K, B>, E> {
+        self.into_iter().map(|(k, v)| f(v).map(|b| (k, b))).collect()
+    }
+}
+
+/// Mapping over a set collects the results into a fresh set. If the
+/// function isn't injective, the result may have fewer elements than
+/// the original.
+impl<'a, A, B: Eq + Hash, S> Covariant<'a, B> for HashSet<A, S>
+    where S: BuildHasher + Default {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> HashSet<B, S> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B: Eq + Hash, S> CovariantRef<'a, B> for HashSet<A, S>
+    where S: BuildHasher + Default {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> HashSet<B, S> {
+        self.iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B: Eq + Hash, S> Traversable<'a, B> for HashSet<A, S>
+    where S: BuildHasher + Default {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<HashSet<B, S>, E> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+/// Mapping over a set collects the results into a fresh set. If the
+/// function isn't injective, the result may have fewer elements than
+/// the original.
+impl<'a, A, B: Ord> Covariant<'a, B> for BTreeSet<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> BTreeSet<B> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B: Ord> CovariantRef<'a, B> for BTreeSet<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> BTreeSet<B> {
+        self.iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B: Ord> Traversable<'a, B> for BTreeSet<A> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<BTreeSet<B>, E> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+/// Mapping over a heap collects the results into a fresh heap, ordered
+/// by the mapped values rather than the original ones.
+impl<'a, A, B: Ord> Covariant<'a, B> for BinaryHeap<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> BinaryHeap<B> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B: Ord> CovariantRef<'a, B> for BinaryHeap<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> BinaryHeap<B> {
+        self.iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B: Ord> Traversable<'a, B> for BinaryHeap<A> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<BinaryHeap<B>, E> {
+        self.into_iter().map(f).collect()
+    }
+}
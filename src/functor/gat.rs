@@ -0,0 +1,163 @@
+//! Functors with generic associated types.
+//!
+//! `Covariant` names the result of `fmap` through `ReParam<B>`, one
+//! impl per target type `B`, so generic code can't require that the
+//! result of one `fmap` can be mapped again without a bound for every
+//! step. A `Functor` names it through the generic associated type
+//! `Rebind<B>`, which is itself a `Functor`. Generic code can then map
+//! as often as it likes, with no `Coyoneda` in between:
+//!
+//! ```rust
+//! use coyoneda::functor::gat::Functor;
+//!
+//! fn add_and_to_string<F>(x: F) -> <F::Rebind<i32> as Functor>::Rebind<String>
+//!     where F: Functor<Param=i32>,
+//! {
+//!     x.fmap(|n| n + 1).fmap(|n| n.to_string())
+//! }
+//!
+//! assert_eq!(add_and_to_string(Some(41)), Some("42".to_string()));
+//! assert_eq!(add_and_to_string(vec![1, 2]), ["2", "3"]);
+//! ```
+//!
+//! `Coyoneda` remains the way to map over types that only implement
+//! the older traits, and over types that aren't functors at all. `Gat`
+//! adapts a `Functor` to the older traits, so it can be suspended in a
+//! `Coyoneda` too.
+
+use std::collections::VecDeque;
+
+use crate::functor::Covariant;
+use crate::functor::parametric::{Param, ReParam};
+
+/// A type with a parameter that can be mapped over.
+///
+/// The function is `FnMut`, so that containers can call it once for
+/// every element.
+pub trait Functor {
+    type Param;
+    type Rebind<B>: Functor<Param=B>;
+
+    fn fmap<B, F: FnMut(Self::Param) -> B>(self, f: F) -> Self::Rebind<B>;
+}
+
+impl<A> Functor for Option<A> {
+    type Param = A;
+    type Rebind<B> = Option<B>;
+
+    fn fmap<B, F: FnMut(A) -> B>(self, f: F) -> Option<B> {
+        self.map(f)
+    }
+}
+
+impl<A, E> Functor for Result<A, E> {
+    type Param = A;
+    type Rebind<B> = Result<B, E>;
+
+    fn fmap<B, F: FnMut(A) -> B>(self, f: F) -> Result<B, E> {
+        self.map(f)
+    }
+}
+
+impl<A> Functor for Box<A> {
+    type Param = A;
+    type Rebind<B> = Box<B>;
+
+    fn fmap<B, F: FnMut(A) -> B>(self, mut f: F) -> Box<B> {
+        Box::new(f(*self))
+    }
+}
+
+impl<X, A> Functor for (X, A) {
+    type Param = A;
+    type Rebind<B> = (X, B);
+
+    fn fmap<B, F: FnMut(A) -> B>(self, mut f: F) -> (X, B) {
+        (self.0, f(self.1))
+    }
+}
+
+impl<A> Functor for Vec<A> {
+    type Param = A;
+    type Rebind<B> = Vec<B>;
+
+    fn fmap<B, F: FnMut(A) -> B>(self, f: F) -> Vec<B> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<A> Functor for VecDeque<A> {
+    type Param = A;
+    type Rebind<B> = VecDeque<B>;
+
+    fn fmap<B, F: FnMut(A) -> B>(self, f: F) -> VecDeque<B> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+/// Adapts a `Functor` to `Param`, `ReParam` and `Covariant`, so that it
+/// can be used wherever those are expected, such as in a `Coyoneda`.
+///
+/// There is no adapter the other way around: an impl of `Functor`
+/// needs `ReParam<B>` for every `B` at once, which the older traits
+/// can't promise.
+///
+/// # Example
+///
+/// ```rust
+/// use coyoneda::Coyoneda;
+/// use coyoneda::functor::Covariant;
+/// use coyoneda::functor::gat::Gat;
+///
+/// let c = Coyoneda::from(Gat(vec![1, 2])).fmap(|n: i32| n * 2);
+/// assert_eq!(c.unwrap().0, [2, 4]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Gat<T>(pub T);
+
+impl<T: Functor> Param for Gat<T> {
+    type Param = T::Param;
+}
+
+impl<T: Functor, B> ReParam<B> for Gat<T> {
+    type Output = Gat<T::Rebind<B>>;
+}
+
+impl<'a, T: Functor, B> Covariant<'a, B> for Gat<T> {
+    fn fmap<F: 'a + Fn(T::Param) -> B>(self, f: F) -> Gat<T::Rebind<B>> {
+        Gat(self.0.fmap(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::{Functor, Gat};
+    use crate::Coyoneda;
+    use crate::functor::Covariant;
+
+    fn twice<F>(x: F) -> <F::Rebind<u64> as Functor>::Rebind<String>
+        where F: Functor<Param=u64>,
+    {
+        x.fmap(|n| n * 2).fmap(|n| format!("<{}>", n))
+    }
+
+    #[test]
+    fn fmap_twice_generically() {
+        assert_eq!(twice(None), None);
+        assert_eq!(twice(Ok::<_, ()>(1)), Ok("<2>".to_string()));
+        assert_eq!(twice(Box::new(2)), Box::new("<4>".to_string()));
+        assert_eq!(twice(('x', 3)), ('x', "<6>".to_string()));
+        assert_eq!(twice(VecDeque::from(vec![4, 5])), ["<8>", "<10>"]);
+    }
+
+    #[test]
+    fn coyoneda_over_gat() {
+        let mut calls = 0;
+        let ys = Functor::fmap(vec![1u64, 2, 3], |n| { calls += 1; n + 1 });
+        assert_eq!(calls, 3);
+        let c = Coyoneda::from(Gat(ys)).fmap(|n: u64| n * 10).fmap(|n: u64| n.to_string());
+        assert_eq!(c.unwrap(), Gat(vec!["20".to_string(), "30".to_string(), "40".to_string()]));
+    }
+}
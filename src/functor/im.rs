@@ -0,0 +1,84 @@
+//! Instances for the persistent collections of the `im` crate, available
+//! with the `im` feature.
+//!
+//! Mapping builds a new collection; the original keeps sharing its
+//! structure with any other clones of it.
+
+use std::hash::{BuildHasher, Hash};
+
+use im::{HashMap, Vector};
+
+use super::{Covariant, CovariantRef, Traversable};
+use super::parametric::{Param, ReParam};
+
+impl<A: Clone> Param for Vector<A> {
+    type Param = A;
+}
+
+impl<A: Clone, B: Clone> ReParam<B> for Vector<A> {
+    type Output = Vector<B>;
+}
+
+impl<'a, A: Clone, B: Clone> Covariant<'a, B> for Vector<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Vector<B> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<'a, A: Clone, B: Clone> CovariantRef<'a, B> for Vector<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Vector<B> {
+        self.iter().map(f).collect()
+    }
+}
+
+impl<'a, A: Clone, B: Clone> Traversable<'a, B> for Vector<A> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<Vector<B>, E> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<K, V, S> Param for HashMap<K, V, S> {
+    type Param = V;
+}
+
+impl<K, V, B, S> ReParam<B> for HashMap<K, V, S> {
+    type Output = HashMap<K, B, S>;
+}
+
+impl<'a, K, V, B, S> Covariant<'a, B> for HashMap<K, V, S>
+    where K: Clone + Eq + Hash, V: Clone, B: Clone, S: BuildHasher + Default {
+    fn fmap<F: 'a + Fn(V) -> B>(self, f: F) -> HashMap<K, B, S> {
+        self.into_iter().map(|(k, v)| (k, f(v))).collect()
+    }
+}
+
+impl<'a, K, V, B, S> CovariantRef<'a, B> for HashMap<K, V, S>
+    where K: Clone + Eq + Hash, B: Clone, S: BuildHasher + Default {
+    fn fmap_ref<F: 'a + Fn(&'a V) -> B>(&'a self, f: F) -> HashMap<K, B, S> {
+        self.iter().map(|(k, v)| (k.clone(), f(v))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use im::{HashMap, Vector};
+
+    use crate::Coyoneda;
+    use crate::functor::Covariant;
+
+    #[test]
+    fn fmap_vector() {
+        let x: Vector<i32> = vec![1, 2, 3].into_iter().collect();
+        let y = Coyoneda::from(x.clone()).fmap(|n: i32| n * 2).unwrap();
+        assert_eq!(y, vec![2, 4, 6].into_iter().collect());
+        assert_eq!(x.len(), 3)
+    }
+
+    #[test]
+    fn fmap_hash_map() {
+        let x: HashMap<&str, i32> = HashMap::unit("a", 1).update("b", 2);
+        let y = Coyoneda::from(x).fmap(|n: i32| n.to_string()).unwrap();
+        assert_eq!(y.get("a"), Some(&"1".to_string()));
+        assert_eq!(y.get("b"), Some(&"2".to_string()))
+    }
+}
@@ -0,0 +1,644 @@
+//! Covariant, contravariant, invariant and bivariant functors.
+//!
+//! Vendored from the `functor` crate, so that instances for standard
+//! library types can live alongside `Coyoneda`.
+//!
+//! The `gat` module has a `Functor` trait built on generic associated
+//! types, which generic code can map over more than once.
+//!
+//! The traits, and instances for the types in the prelude, are always
+//! available. Other instances are behind features, as listed in the
+//! `core` module.
+
+pub mod gat;
+pub mod isomorphism;
+pub mod parametric;
+
+#[cfg(feature = "std-collections")]
+mod collections;
+#[cfg(feature = "im")]
+mod im;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+#[cfg(feature = "smallvec")]
+mod smallvec;
+#[cfg(feature = "sync")]
+mod sync;
+
+use self::isomorphism::{Iso, IsoOnce};
+use self::parametric::{BiReParam, Param, ReParam};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::iter::{self, Empty};
+use std::ops::{Bound, ControlFlow};
+use std::option;
+use std::rc::Rc;
+use std::task::Poll;
+
+pub trait Covariant<'a, B>: ReParam<B> {
+    fn fmap<F: 'a + Fn(Self::Param) -> B>(self, f: F) -> Self::Output;
+}
+
+pub trait CovariantOnce<'a, B>: ReParam<B> + Covariant<'a, B> {
+    fn fmap_once<F: 'a + FnOnce(Self::Param) -> B>(self, f: F) -> Self::Output;
+}
+
+/// Covariant functors that can be mapped over by reference, building a
+/// new container and leaving the original untouched.
+pub trait CovariantRef<'a, B>: ReParam<B> {
+    fn fmap_ref<F: 'a + Fn(&'a Self::Param) -> B>(&'a self, f: F) -> Self::Output;
+}
+
+/// Covariant functors that can combine two values of the same shape,
+/// one with parameter `Self::Param` and one with parameter `B`, into a
+/// value with parameter `C`.
+pub trait Applicative<'a, B, C>: ReParam<B> + ReParam<C> {
+    fn map2<F: 'a + Fn(Self::Param, B) -> C>(self, other: <Self as ReParam<B>>::Output, f: F) -> <Self as ReParam<C>>::Output;
+}
+
+/// Covariant functors whose values can be sequenced, with each step
+/// choosing the next value based on the parameter of the previous one.
+pub trait Monad<'a, B>: ReParam<B> {
+    fn bind<F: 'a + Fn(Self::Param) -> Self::Output>(self, f: F) -> Self::Output;
+}
+
+/// Functors that can wrap a single value without any further effect.
+pub trait Pure: Param {
+    fn pure(x: Self::Param) -> Self;
+}
+
+/// Covariant functors that can be mapped over with a fallible function,
+/// stopping at the first failure.
+pub trait Traversable<'a, B>: ReParam<B> {
+    fn traverse<E, F: 'a + Fn(Self::Param) -> Result<B, E>>(self, f: F) -> Result<Self::Output, E>;
+
+    fn traverse_option<F: 'a + Fn(Self::Param) -> Option<B>>(self, f: F) -> Option<Self::Output>
+        where Self: Sized {
+        self.traverse(move |a| f(a).ok_or(())).ok()
+    }
+}
+
+/// Functors whose contents can be combined into a summary value, from
+/// left to right.
+pub trait Foldable: Param {
+    fn fold<B, F: Fn(B, Self::Param) -> B>(self, init: B, f: F) -> B;
+}
+
+/// Functors that always hold a value that can be taken out.
+pub trait Extract: Param {
+    fn extract(self) -> Self::Param;
+}
+
+/// The dual of `Monad`: `extend` computes a new value at every position
+/// from the context around it.
+pub trait Comonad<'a, B>: Extract + ReParam<B> {
+    fn extend<F: 'a + Fn(&Self) -> B>(&self, f: F) -> Self::Output;
+}
+
+/// Types that are covariant in both of their parameters.
+pub trait Bifunctor<'a, C, D>: BiReParam<C, D> {
+    fn bimap<F, G>(self, f: F, g: G) -> Self::Output
+        where F: 'a + Fn(Self::First) -> C, G: 'a + Fn(Self::Second) -> D;
+}
+
+pub trait Contravariant<'a, B>: ReParam<B> {
+    fn contramap<F: 'a + Fn(B) -> Self::Param>(self, f: F) -> Self::Output;
+}
+
+pub trait ContravariantOnce<'a, B>: ReParam<B> + Contravariant<'a, B> {
+    fn contramap_once<F: 'a + FnOnce(B) -> Self::Param>(self, f: F) -> Self::Output;
+}
+
+pub trait Invariant<'a, B>: ReParam<B> {
+    fn invmap<F: 'a + Iso<Self::Param, B>>(self, f: F) -> Self::Output;
+}
+
+pub trait InvariantOnce<'a, B>: ReParam<B> + Invariant<'a, B> {
+    fn invmap_once<F: 'a + IsoOnce<Self::Param, B>>(self, f: F) -> Self::Output;
+}
+
+pub trait Bivariant<'a, B>: ReParam<B> {
+    fn xmap(self) -> Self::Output;
+}
+
+pub trait NaturalTransform<T: Param<Param=Self::Param>>: Param {
+    fn transform(self) -> T;
+}
+
+impl<'a, A, B> Covariant<'a, B> for std::option::Option<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Self::Output {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, A, B> CovariantOnce<'a, B> for std::option::Option<A> {
+    fn fmap_once<F: 'a + FnOnce(A) -> B>(self, f: F) -> Self::Output {
+        self.map(f)
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for std::option::Option<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Self::Output {
+        self.as_ref().map(f)
+    }
+}
+
+impl<'a, A, B, C> Applicative<'a, B, C> for std::option::Option<A> {
+    fn map2<F: 'a + Fn(A, B) -> C>(self, other: Option<B>, f: F) -> Option<C> {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(f(a, b)),
+            _ => None
+        }
+    }
+}
+
+impl<'a, A, B> Monad<'a, B> for std::option::Option<A> {
+    fn bind<F: 'a + Fn(A) -> Option<B>>(self, f: F) -> Option<B> {
+        self.and_then(f)
+    }
+}
+
+impl<A> Pure for std::option::Option<A> {
+    fn pure(x: A) -> Option<A> {
+        Some(x)
+    }
+}
+
+impl<'a, A, B> Traversable<'a, B> for std::option::Option<A> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<Option<B>, E> {
+        match self {
+            Some(a) => f(a).map(Some),
+            None => Ok(None)
+        }
+    }
+}
+
+impl<A> Foldable for std::option::Option<A> {
+    fn fold<B, F: Fn(B, A) -> B>(self, init: B, f: F) -> B {
+        self.into_iter().fold(init, f)
+    }
+}
+
+impl<'a, X, A, B> Covariant<'a, B> for (X, A) {
+    fn fmap<F: Fn(A) -> B>(self, f: F) -> Self::Output {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, X, A, B> CovariantOnce<'a, B> for (X, A) {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> Self::Output {
+        (self.0, f(self.1))
+    }
+}
+
+impl<'a, X: Clone, A, B> CovariantRef<'a, B> for (X, A) {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Self::Output {
+        (self.0.clone(), f(&self.1))
+    }
+}
+
+impl<'a, X, A, B> Traversable<'a, B> for (X, A) {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<(X, B), E> {
+        let x = self.0;
+        f(self.1).map(|b| (x, b))
+    }
+}
+
+impl<'a, A, B, C, D> Bifunctor<'a, C, D> for (A, B) {
+    fn bimap<F, G>(self, f: F, g: G) -> (C, D)
+        where F: 'a + Fn(A) -> C, G: 'a + Fn(B) -> D {
+        (f(self.0), g(self.1))
+    }
+}
+
+// Larger tuples map over their last component, like pairs do.
+macro_rules! tuple_covariant {
+    ($($X:ident $x:ident),+) => {
+        impl<'a, $($X,)+ A, B> Covariant<'a, B> for ($($X,)+ A) {
+            fn fmap<F: Fn(A) -> B>(self, f: F) -> Self::Output {
+                self.fmap_once(f)
+            }
+        }
+
+        impl<'a, $($X,)+ A, B> CovariantOnce<'a, B> for ($($X,)+ A) {
+            fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> Self::Output {
+                let ($($x,)+ a) = self;
+                ($($x,)+ f(a))
+            }
+        }
+
+        impl<'a, $($X: Clone,)+ A, B> CovariantRef<'a, B> for ($($X,)+ A) {
+            fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Self::Output {
+                let ($(ref $x,)+ ref a) = *self;
+                ($($x.clone(),)+ f(a))
+            }
+        }
+
+        impl<'a, $($X,)+ A, B> Traversable<'a, B> for ($($X,)+ A) {
+            fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<Self::Output, E> {
+                let ($($x,)+ a) = self;
+                f(a).map(move |b| ($($x,)+ b))
+            }
+        }
+    }
+}
+
+tuple_covariant!(X1 x1, X2 x2);
+tuple_covariant!(X1 x1, X2 x2, X3 x3);
+tuple_covariant!(X1 x1, X2 x2, X3 x3, X4 x4);
+tuple_covariant!(X1 x1, X2 x2, X3 x3, X4 x4, X5 x5);
+
+impl<'a, A, B> Covariant<'a, B> for Box<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Self::Output {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, A, B> CovariantOnce<'a, B> for Box<A> {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> Self::Output {
+        Box::new(f(*self))
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for Box<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Self::Output {
+        Box::new(f(self))
+    }
+}
+
+impl<'a, A, B, C> Applicative<'a, B, C> for Box<A> {
+    fn map2<F: 'a + Fn(A, B) -> C>(self, other: Box<B>, f: F) -> Box<C> {
+        Box::new(f(*self, *other))
+    }
+}
+
+impl<'a, A, B> Monad<'a, B> for Box<A> {
+    fn bind<F: 'a + Fn(A) -> Box<B>>(self, f: F) -> Box<B> {
+        f(*self)
+    }
+}
+
+impl<A> Pure for Box<A> {
+    fn pure(x: A) -> Box<A> {
+        Box::new(x)
+    }
+}
+
+impl<'a, A, B> Traversable<'a, B> for Box<A> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<Box<B>, E> {
+        f(*self).map(Box::new)
+    }
+}
+
+impl<A> Foldable for Box<A> {
+    fn fold<B, F: Fn(B, A) -> B>(self, init: B, f: F) -> B {
+        f(init, *self)
+    }
+}
+
+impl<A> Extract for Box<A> {
+    fn extract(self) -> A {
+        *self
+    }
+}
+
+impl<'a, A, B> Covariant<'a, B> for Box<[A]> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Box<[B]> {
+        self.into_vec().into_iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for Box<[A]> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Box<[B]> {
+        self.iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B> Traversable<'a, B> for Box<[A]> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<Box<[B]>, E> {
+        self.into_vec().into_iter().map(f).collect()
+    }
+}
+
+impl<A> Foldable for Box<[A]> {
+    fn fold<B, F: Fn(B, A) -> B>(self, init: B, f: F) -> B {
+        self.into_vec().into_iter().fold(init, f)
+    }
+}
+
+/// Functions map over their result, by post-composition. Lifting a
+/// function into `Coyoneda` accumulates the composed steps in a chain,
+/// so that they are applied in a loop rather than as nested closures.
+impl<'a: 'f, 'f, R: 'f, A: 'f, B> Covariant<'a, B> for Box<dyn Fn(R) -> A + 'f> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Box<dyn Fn(R) -> B + 'f> {
+        Box::new(move |r| f(self(r)))
+    }
+}
+
+/// Mapping over a shared `Rc` clones the inner value, unless this is
+/// the only strong reference to it.
+impl<'a, A: Clone, B> Covariant<'a, B> for Rc<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Self::Output {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, A: Clone, B> CovariantOnce<'a, B> for Rc<A> {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> Self::Output {
+        Rc::new(f(Rc::unwrap_or_clone(self)))
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for Rc<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Self::Output {
+        Rc::new(f(self))
+    }
+}
+
+/// Mapping over a shared slice clones its elements, since they can't be
+/// moved out from behind the `Rc`.
+impl<'a, A: Clone, B> Covariant<'a, B> for Rc<[A]> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Rc<[B]> {
+        self.iter().cloned().map(f).collect()
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for Rc<[A]> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Rc<[B]> {
+        self.iter().map(f).collect()
+    }
+}
+
+/// Mapping over a `Cow` always produces an owned value, cloning the
+/// input first if it is borrowed.
+impl<'a, 'c, A: Clone, B: 'c + Clone> Covariant<'a, B> for Cow<'c, A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Self::Output {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, 'c, A: Clone, B: 'c + Clone> CovariantOnce<'a, B> for Cow<'c, A> {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> Self::Output {
+        Cow::Owned(f(self.into_owned()))
+    }
+}
+
+impl<'a, 'c, A: Clone, B: 'c + Clone> CovariantRef<'a, B> for Cow<'c, A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Self::Output {
+        Cow::Owned(f(self))
+    }
+}
+
+impl<'a, A, B> Covariant<'a, B> for Cell<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Cell<B> {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, A, B> CovariantOnce<'a, B> for Cell<A> {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> Cell<B> {
+        Cell::new(f(self.into_inner()))
+    }
+}
+
+impl<'a, A, B> Covariant<'a, B> for RefCell<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> RefCell<B> {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, A, B> CovariantOnce<'a, B> for RefCell<A> {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> RefCell<B> {
+        RefCell::new(f(self.into_inner()))
+    }
+}
+
+impl<'a, A, B> Covariant<'a, B> for Poll<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Poll<B> {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, A, B> CovariantOnce<'a, B> for Poll<A> {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> Poll<B> {
+        self.map(f)
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for Poll<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Poll<B> {
+        match *self {
+            Poll::Ready(ref a) => Poll::Ready(f(a)),
+            Poll::Pending => Poll::Pending
+        }
+    }
+}
+
+impl<'a, B, C, D> Covariant<'a, D> for ControlFlow<B, C> {
+    fn fmap<F: 'a + Fn(C) -> D>(self, f: F) -> ControlFlow<B, D> {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, B, C, D> CovariantOnce<'a, D> for ControlFlow<B, C> {
+    fn fmap_once<F: FnOnce(C) -> D>(self, f: F) -> ControlFlow<B, D> {
+        self.map_continue(f)
+    }
+}
+
+impl<'a, B, C, D, E> Bifunctor<'a, D, E> for ControlFlow<B, C> {
+    fn bimap<F, G>(self, f: F, g: G) -> ControlFlow<D, E>
+        where F: 'a + Fn(B) -> D, G: 'a + Fn(C) -> E {
+        match self {
+            ControlFlow::Break(b) => ControlFlow::Break(f(b)),
+            ControlFlow::Continue(c) => ControlFlow::Continue(g(c))
+        }
+    }
+}
+
+impl<'a, A, B> Covariant<'a, B> for Bound<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Bound<B> {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, A, B> CovariantOnce<'a, B> for Bound<A> {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> Bound<B> {
+        self.map(f)
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for Bound<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Bound<B> {
+        self.as_ref().map(f)
+    }
+}
+
+impl<'a, A, B, E> Covariant<'a, B> for Result<A, E> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Self::Output {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, A, B, E> CovariantOnce<'a, B> for Result<A, E> {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> Self::Output {
+        self.map(f)
+    }
+}
+
+impl<'a, A, B, E: Clone> CovariantRef<'a, B> for Result<A, E> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Self::Output {
+        self.as_ref().map(f).map_err(E::clone)
+    }
+}
+
+impl<'a, A, B, C, E> Applicative<'a, B, C> for Result<A, E> {
+    fn map2<F: 'a + Fn(A, B) -> C>(self, other: Result<B, E>, f: F) -> Result<C, E> {
+        match (self, other) {
+            (Ok(a), Ok(b)) => Ok(f(a, b)),
+            (Err(e), _) | (_, Err(e)) => Err(e)
+        }
+    }
+}
+
+impl<'a, A, B, E> Monad<'a, B> for Result<A, E> {
+    fn bind<F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<B, E> {
+        self.and_then(f)
+    }
+}
+
+impl<A, E> Pure for Result<A, E> {
+    fn pure(x: A) -> Result<A, E> {
+        Ok(x)
+    }
+}
+
+impl<'a, A, B, X> Traversable<'a, B> for Result<A, X> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<Result<B, X>, E> {
+        match self {
+            Ok(a) => f(a).map(Ok),
+            Err(x) => Ok(Err(x))
+        }
+    }
+}
+
+impl<'a, A, E, B, X> Bifunctor<'a, B, X> for Result<A, E> {
+    fn bimap<F, G>(self, f: F, g: G) -> Result<B, X>
+        where F: 'a + Fn(A) -> B, G: 'a + Fn(E) -> X {
+        self.map(f).map_err(g)
+    }
+}
+
+impl<'a, A, B> Covariant<'a, B> for Vec<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Vec<B> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for Vec<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Vec<B> {
+        self.iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B> Traversable<'a, B> for Vec<A> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<Vec<B>, E> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<A> Foldable for Vec<A> {
+    fn fold<B, F: Fn(B, A) -> B>(self, init: B, f: F) -> B {
+        self.into_iter().fold(init, f)
+    }
+}
+
+impl<'a, A, B, const N: usize> Covariant<'a, B> for [A; N] {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> [B; N] {
+        self.map(f)
+    }
+}
+
+impl<'a, A, B, const N: usize> CovariantRef<'a, B> for [A; N] {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> [B; N] {
+        self.each_ref().map(f)
+    }
+}
+
+impl<'a, A, B> Covariant<'a, B> for option::IntoIter<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(mut self, f: F) -> option::IntoIter<B> {
+        self.next().map(f).into_iter()
+    }
+}
+
+impl<'a, A, B> Covariant<'a, B> for Empty<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, _: F) -> Empty<B> {
+        iter::empty()
+    }
+}
+
+impl<'a, A, B> Bivariant<'a, B> for std::marker::PhantomData<A> {
+    fn xmap(self) -> Self::Output { std::marker::PhantomData }
+}
+
+/// The empty functor: mapping only changes the phantom parameter, and
+/// the function is never called.
+impl<'a, A, B> Covariant<'a, B> for std::marker::PhantomData<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, _: F) -> std::marker::PhantomData<B> {
+        std::marker::PhantomData
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for std::marker::PhantomData<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, _: F) -> std::marker::PhantomData<B> {
+        std::marker::PhantomData
+    }
+}
+
+impl<'a, A, B> Traversable<'a, B> for std::marker::PhantomData<A> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, _: F) -> Result<std::marker::PhantomData<B>, E> {
+        Ok(std::marker::PhantomData)
+    }
+}
+
+impl<A> Foldable for std::marker::PhantomData<A> {
+    fn fold<B, F: Fn(B, A) -> B>(self, init: B, _: F) -> B {
+        init
+    }
+}
+
+impl<A> NaturalTransform<Option<A>> for Box<A> {
+    fn transform(self) -> Option<A> {
+        Option::Some(*self)
+    }
+}
+
+impl<A, E> NaturalTransform<Option<A>> for Result<A, E> {
+    fn transform(self) -> Option<A> {
+        self.ok()
+    }
+}
+
+impl<A: Clone> NaturalTransform<Box<A>> for Rc<A> {
+    fn transform(self) -> Box<A> {
+        Box::new(Rc::unwrap_or_clone(self))
+    }
+}
+
+impl<A> NaturalTransform<Vec<A>> for Option<A> {
+    fn transform(self) -> Vec<A> {
+        self.into_iter().collect()
+    }
+}
+
+impl<A, E: Default> NaturalTransform<Result<A, E>> for Option<A> {
+    fn transform(self) -> Result<A, E> {
+        self.ok_or_else(E::default)
+    }
+}
+
+impl<A> NaturalTransform<Option<A>> for Vec<A> {
+    fn transform(self) -> Option<A> {
+        self.into_iter().next()
+    }
+}
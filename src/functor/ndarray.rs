@@ -0,0 +1,64 @@
+//! Elementwise instances for `ndarray::Array`, available with the
+//! `ndarray` feature.
+//!
+//! A `Coyoneda` over an array fuses every suspended transform into a
+//! single pass over the elements when it is lowered.
+
+use ndarray::{Array, Dimension};
+
+use super::{Covariant, CovariantRef, Traversable};
+use super::parametric::{Param, ReParam};
+
+impl<A, D: Dimension> Param for Array<A, D> {
+    type Param = A;
+}
+
+impl<A, B, D: Dimension> ReParam<B> for Array<A, D> {
+    type Output = Array<B, D>;
+}
+
+impl<'a, A, B, D: Dimension> Covariant<'a, B> for Array<A, D> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Array<B, D> {
+        let dim = self.raw_dim();
+        let elems = self.into_iter().map(f).collect();
+        match Array::from_shape_vec(dim, elems) {
+            Ok(res) => res,
+            Err(_) => unreachable!()
+        }
+    }
+}
+
+impl<'a, A, B, D: Dimension> CovariantRef<'a, B> for Array<A, D> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Array<B, D> {
+        self.map(f)
+    }
+}
+
+impl<'a, A, B, D: Dimension> Traversable<'a, B> for Array<A, D> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<Array<B, D>, E> {
+        let dim = self.raw_dim();
+        let elems = self.into_iter().map(f).collect::<Result<_, E>>()?;
+        match Array::from_shape_vec(dim, elems) {
+            Ok(res) => Ok(res),
+            Err(_) => unreachable!()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{arr2, Array2};
+
+    use crate::Coyoneda;
+    use crate::functor::Covariant;
+
+    #[test]
+    fn fmap_array() {
+        let x = arr2(&[[1, 2, 3], [4, 5, 6]]);
+        let y: Array2<f64> = Coyoneda::from(x.t().to_owned())
+            .fmap(|n: i32| n * 2)
+            .fmap(|n| n as f64 + 0.5)
+            .unwrap();
+        assert_eq!(y, arr2(&[[2.5, 8.5], [4.5, 10.5], [6.5, 12.5]]))
+    }
+}
@@ -0,0 +1,230 @@
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::iter::Empty;
+use std::ops::{Bound, ControlFlow};
+use std::option::{self, Option};
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::task::Poll;
+
+pub trait Param {
+    type Param;
+}
+
+pub trait ReParam<B>: Param {
+    type Output: Param<Param=B>;
+}
+
+/// Types with two parameters, named by their position.
+pub trait BiParam {
+    type First;
+    type Second;
+}
+
+pub trait BiReParam<C, D>: BiParam {
+    type Output: BiParam<First=C, Second=D>;
+}
+
+impl<A: ?Sized + Param> Param for &A {
+    type Param = A::Param;
+}
+
+impl<A: ?Sized + Param> Param for &mut A {
+    type Param = A::Param;
+}
+
+impl<A> Param for Option<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for Option<A> {
+    type Output = Option<B>;
+}
+
+impl<X, A> Param for (X, A) {
+    type Param = A;
+}
+
+impl<X, A, B> ReParam<B> for (X, A) {
+    type Output = (X, B);
+}
+
+macro_rules! tuple_param {
+    ($($X:ident),+) => {
+        impl<$($X,)+ A> Param for ($($X,)+ A) {
+            type Param = A;
+        }
+
+        impl<$($X,)+ A, B> ReParam<B> for ($($X,)+ A) {
+            type Output = ($($X,)+ B);
+        }
+    }
+}
+
+tuple_param!(X1, X2);
+tuple_param!(X1, X2, X3);
+tuple_param!(X1, X2, X3, X4);
+tuple_param!(X1, X2, X3, X4, X5);
+
+impl<A, B> BiParam for (A, B) {
+    type First = A;
+    type Second = B;
+}
+
+impl<A, B, C, D> BiReParam<C, D> for (A, B) {
+    type Output = (C, D);
+}
+
+impl<A> Param for option::IntoIter<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for option::IntoIter<A> {
+    type Output = option::IntoIter<B>;
+}
+
+impl<A> Param for Empty<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for Empty<A> {
+    type Output = Empty<B>;
+}
+
+impl<A> Param for PhantomData<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for PhantomData<A> {
+    type Output = PhantomData<B>;
+}
+
+impl<A> Param for Box<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for Box<A> {
+    type Output = Box<B>;
+}
+
+impl<A> Param for Box<[A]> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for Box<[A]> {
+    type Output = Box<[B]>;
+}
+
+impl<'f, R, A> Param for Box<dyn Fn(R) -> A + 'f> {
+    type Param = A;
+}
+
+impl<'f, R, A, B> ReParam<B> for Box<dyn Fn(R) -> A + 'f> {
+    type Output = Box<dyn Fn(R) -> B + 'f>;
+}
+
+impl<A, E> Param for Result<A, E> {
+    type Param = A;
+}
+
+impl<A, B, E> ReParam<B> for Result<A, E> {
+    type Output = Result<B, E>;
+}
+
+impl<A, E> BiParam for Result<A, E> {
+    type First = A;
+    type Second = E;
+}
+
+impl<A, E, B, F> BiReParam<B, F> for Result<A, E> {
+    type Output = Result<B, F>;
+}
+
+impl<A> Param for Vec<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for Vec<A> {
+    type Output = Vec<B>;
+}
+
+impl<A> Param for Rc<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for Rc<A> {
+    type Output = Rc<B>;
+}
+
+impl<A> Param for Rc<[A]> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for Rc<[A]> {
+    type Output = Rc<[B]>;
+}
+
+impl<'c, A: Clone> Param for Cow<'c, A> {
+    type Param = A;
+}
+
+impl<'c, A: Clone, B: 'c + Clone> ReParam<B> for Cow<'c, A> {
+    type Output = Cow<'c, B>;
+}
+
+impl<A, const N: usize> Param for [A; N] {
+    type Param = A;
+}
+
+impl<A, B, const N: usize> ReParam<B> for [A; N] {
+    type Output = [B; N];
+}
+
+impl<A> Param for Cell<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for Cell<A> {
+    type Output = Cell<B>;
+}
+
+impl<A> Param for RefCell<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for RefCell<A> {
+    type Output = RefCell<B>;
+}
+
+impl<A> Param for Poll<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for Poll<A> {
+    type Output = Poll<B>;
+}
+
+impl<B, C> Param for ControlFlow<B, C> {
+    type Param = C;
+}
+
+impl<B, C, D> ReParam<D> for ControlFlow<B, C> {
+    type Output = ControlFlow<B, D>;
+}
+
+impl<B, C> BiParam for ControlFlow<B, C> {
+    type First = B;
+    type Second = C;
+}
+
+impl<B, C, D, E> BiReParam<D, E> for ControlFlow<B, C> {
+    type Output = ControlFlow<D, E>;
+}
+
+impl<A> Param for Bound<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for Bound<A> {
+    type Output = Bound<B>;
+}
@@ -0,0 +1,55 @@
+//! Instances for `SmallVec`, available with the `smallvec` feature.
+//!
+//! Mapping collects into a `SmallVec` with the same inline capacity, so
+//! short vectors stay on the stack through lowering.
+
+use smallvec::{Array, SmallVec};
+
+use super::{Covariant, CovariantRef, Traversable};
+use super::parametric::{Param, ReParam};
+
+impl<A, const N: usize> Param for SmallVec<[A; N]> where [A; N]: Array<Item=A> {
+    type Param = A;
+}
+
+impl<A, B, const N: usize> ReParam<B> for SmallVec<[A; N]>
+    where [A; N]: Array<Item=A>, [B; N]: Array<Item=B> {
+    type Output = SmallVec<[B; N]>;
+}
+
+impl<'a, A, B, const N: usize> Covariant<'a, B> for SmallVec<[A; N]>
+    where [A; N]: Array<Item=A>, [B; N]: Array<Item=B> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> SmallVec<[B; N]> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B, const N: usize> CovariantRef<'a, B> for SmallVec<[A; N]>
+    where [A; N]: Array<Item=A>, [B; N]: Array<Item=B> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> SmallVec<[B; N]> {
+        self.iter().map(f).collect()
+    }
+}
+
+impl<'a, A, B, const N: usize> Traversable<'a, B> for SmallVec<[A; N]>
+    where [A; N]: Array<Item=A>, [B; N]: Array<Item=B> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<SmallVec<[B; N]>, E> {
+        self.into_iter().map(f).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use smallvec::SmallVec;
+
+    use crate::Coyoneda;
+    use crate::functor::Covariant;
+
+    #[test]
+    fn fmap_inline() {
+        let x: SmallVec<[i32; 4]> = SmallVec::from_slice(&[1, 2, 3]);
+        let y = Coyoneda::from(x).fmap(|n: i32| n * 2).fmap(|n| n + 1).unwrap();
+        assert!(!y.spilled());
+        assert_eq!(&y[..], &[3, 5, 7])
+    }
+}
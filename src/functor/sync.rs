@@ -0,0 +1,86 @@
+//! Instances for `Arc` and the locks in `std::sync`, available with the
+//! `sync` feature, which is on by default.
+
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+use super::{Covariant, CovariantOnce, CovariantRef, NaturalTransform};
+use super::parametric::{Param, ReParam};
+
+impl<A> Param for Arc<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for Arc<A> {
+    type Output = Arc<B>;
+}
+
+impl<A> Param for Mutex<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for Mutex<A> {
+    type Output = Mutex<B>;
+}
+
+impl<A> Param for RwLock<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for RwLock<A> {
+    type Output = RwLock<B>;
+}
+
+/// Mapping over a shared `Arc` clones the inner value, unless this is
+/// the only strong reference to it. Weak references don't count, and
+/// are left dangling once the original allocation is dropped.
+impl<'a, A: Clone, B> Covariant<'a, B> for Arc<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Self::Output {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, A: Clone, B> CovariantOnce<'a, B> for Arc<A> {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> Self::Output {
+        Arc::new(f(Arc::unwrap_or_clone(self)))
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for Arc<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> Self::Output {
+        Arc::new(f(self))
+    }
+}
+
+/// Mapping over a lock consumes it. A poisoned lock is not an error here:
+/// the inner value is recovered as-is, and the new lock starts unpoisoned.
+impl<'a, A, B> Covariant<'a, B> for Mutex<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Mutex<B> {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, A, B> CovariantOnce<'a, B> for Mutex<A> {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> Mutex<B> {
+        Mutex::new(f(self.into_inner().unwrap_or_else(PoisonError::into_inner)))
+    }
+}
+
+/// Mapping over a lock consumes it. A poisoned lock is not an error here:
+/// the inner value is recovered as-is, and the new lock starts unpoisoned.
+impl<'a, A, B> Covariant<'a, B> for RwLock<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> RwLock<B> {
+        self.fmap_once(f)
+    }
+}
+
+impl<'a, A, B> CovariantOnce<'a, B> for RwLock<A> {
+    fn fmap_once<F: FnOnce(A) -> B>(self, f: F) -> RwLock<B> {
+        RwLock::new(f(self.into_inner().unwrap_or_else(PoisonError::into_inner)))
+    }
+}
+
+impl<A: Clone> NaturalTransform<Box<A>> for Arc<A> {
+    fn transform(self) -> Box<A> {
+        Box::new(Arc::unwrap_or_clone(self))
+    }
+}
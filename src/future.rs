@@ -0,0 +1,69 @@
+//! A functor over futures, available with the `futures` feature.
+//!
+//! `MappedFuture` wraps any `Future`, so it can be lifted into
+//! `Coyoneda`. Lowering the suspension yields a future that applies the
+//! accumulated chain to the output once it is ready.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::FutureExt;
+
+use crate::functor::{Covariant, CovariantOnce};
+use crate::functor::parametric::{Param, ReParam};
+
+pub struct MappedFuture<'a, A> {
+    inner: Pin<Box<dyn Future<Output=A> + 'a>>
+}
+
+impl<'a, A> MappedFuture<'a, A> {
+    pub fn new<F: Future<Output=A> + 'a>(future: F) -> MappedFuture<'a, A> {
+        MappedFuture{inner: Box::pin(future)}
+    }
+}
+
+impl<'a, A> Future for MappedFuture<'a, A> {
+    type Output = A;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<A> {
+        self.inner.as_mut().poll(cx)
+    }
+}
+
+impl<'a, A> Param for MappedFuture<'a, A> {
+    type Param = A;
+}
+
+impl<'a, A, B> ReParam<B> for MappedFuture<'a, A> {
+    type Output = MappedFuture<'a, B>;
+}
+
+impl<'b: 'a, 'a, A: 'a, B> Covariant<'b, B> for MappedFuture<'a, A> {
+    fn fmap<F: 'b + Fn(A) -> B>(self, f: F) -> MappedFuture<'a, B> {
+        self.fmap_once(f)
+    }
+}
+
+impl<'b: 'a, 'a, A: 'a, B> CovariantOnce<'b, B> for MappedFuture<'a, A> {
+    fn fmap_once<F: 'b + FnOnce(A) -> B>(self, f: F) -> MappedFuture<'a, B> {
+        MappedFuture::new(self.inner.map(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use futures::future;
+
+    use super::MappedFuture;
+    use crate::Coyoneda;
+    use crate::functor::Covariant;
+
+    #[test]
+    fn map_future() {
+        let x = MappedFuture::new(future::ready(42));
+        let y = Coyoneda::from(x).fmap(|n: i32| n + 1).fmap(|n| n.to_string());
+        assert_eq!(block_on(y.unwrap()), "43")
+    }
+}
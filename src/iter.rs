@@ -0,0 +1,58 @@
+//! A functor over iterators.
+//!
+//! `Mapped` wraps any iterator, so it can be lifted into `Coyoneda`.
+//! Mapping is lazy: lowering the suspension yields an iterator that
+//! applies the accumulated chain to each item as it is pulled.
+
+use crate::functor::Covariant;
+use crate::functor::parametric::{Param, ReParam};
+
+pub struct Mapped<'a, A> {
+    inner: Box<dyn Iterator<Item=A> + 'a>
+}
+
+impl<'a, A> Mapped<'a, A> {
+    pub fn new<I: IntoIterator<Item=A>>(iter: I) -> Mapped<'a, A> where I::IntoIter: 'a {
+        Mapped{inner: Box::new(iter.into_iter())}
+    }
+}
+
+impl<'a, A> Iterator for Mapped<'a, A> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<A> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, A> Param for Mapped<'a, A> {
+    type Param = A;
+}
+
+impl<'a, A, B> ReParam<B> for Mapped<'a, A> {
+    type Output = Mapped<'a, B>;
+}
+
+impl<'b: 'a, 'a, A: 'a, B> Covariant<'b, B> for Mapped<'a, A> {
+    fn fmap<F: 'b + Fn(A) -> B>(self, f: F) -> Mapped<'a, B> {
+        Mapped::new(self.inner.map(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mapped;
+    use crate::Coyoneda;
+    use crate::functor::Covariant;
+
+    #[test]
+    fn map_lazily() {
+        let x = Mapped::new(1..);
+        let y = Coyoneda::from(x).fmap(|n: u64| n * n).fmap(|n| n.to_string());
+        assert_eq!(y.unwrap().take(3).collect::<Vec<_>>(), vec!["1", "4", "9"])
+    }
+}
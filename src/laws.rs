@@ -0,0 +1,141 @@
+//! Assertions for the laws that functor instances are expected to obey,
+//! for use in tests of hand-written instances.
+//!
+//! Each helper checks its law for one value, and panics with both sides
+//! of the equation when it doesn't hold.
+
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use crate::functor::{Covariant, NaturalTransform};
+use crate::functor::parametric::{Param, ReParam};
+
+/// Assert that mapping the identity function over `fa` returns a value
+/// equal to `fa`.
+///
+/// # Example
+///
+/// ```rust
+/// use coyoneda::laws::assert_functor_identity;
+///
+/// assert_functor_identity(vec![1, 2, 3]);
+/// ```
+pub fn assert_functor_identity<'a, T>(fa: T)
+    where T: Covariant<'a, <T as Param>::Param, Output=T> + Clone + PartialEq + Debug,
+{
+    let mapped = fa.clone().fmap(|a| a);
+    assert_eq!(mapped, fa, "mapping the identity function changed the value");
+}
+
+/// Assert that mapping `f` and then `g` over `fa` gives the same result
+/// as mapping their composition.
+///
+/// # Example
+///
+/// ```rust
+/// use coyoneda::laws::assert_functor_composition;
+///
+/// assert_functor_composition(Some(20), |n: i32| n + 1, |n: i32| n * 2);
+/// ```
+pub fn assert_functor_composition<'a, T, B, C, F, G>(fa: T, f: F, g: G)
+    where T: Covariant<'a, B> + Covariant<'a, C> + Clone,
+          <T as ReParam<B>>::Output: Covariant<'a, C, Output=<T as ReParam<C>>::Output>,
+          <T as ReParam<C>>::Output: PartialEq + Debug,
+          F: Fn(T::Param) -> B + 'a,
+          G: Fn(B) -> C + 'a,
+          T::Param: 'a, B: 'a,
+{
+    let (f, g) = (Rc::new(f), Rc::new(g));
+    let (f1, g1) = (f.clone(), g.clone());
+    let stepwise = Covariant::<'a, B>::fmap(fa.clone(), move |a| f1(a)).fmap(move |b| g1(b));
+    let composed = Covariant::<'a, C>::fmap(fa, move |a| g(f(a)));
+    assert_eq!(stepwise, composed, "mapping two functions in turn differs from mapping their composition");
+}
+
+/// Assert that transforming `fa` from `F` to `G` commutes with mapping
+/// `f` over it: transforming and then mapping gives the same result as
+/// mapping and then transforming.
+///
+/// This is the law that lets `Coyoneda::natural_transform_to` move a
+/// transformation in front of the suspended functions.
+///
+/// # Example
+///
+/// ```rust
+/// use coyoneda::laws::assert_naturality;
+///
+/// assert_naturality::<Box<i32>, Option<i32>, _>(Box::new(41), |n| n + 1);
+/// ```
+pub fn assert_naturality<'a, F, G, B>(fa: F, f: impl Fn(F::Param) -> B + 'a)
+    where F: NaturalTransform<G> + Covariant<'a, B> + Clone,
+          G: Param<Param=F::Param> + Covariant<'a, B>,
+          <F as ReParam<B>>::Output: NaturalTransform<<G as ReParam<B>>::Output>,
+          <G as ReParam<B>>::Output: PartialEq + Debug,
+          F::Param: 'a,
+{
+    let f = Rc::new(f);
+    let f1 = f.clone();
+    let mapped_first: <G as ReParam<B>>::Output = fa.clone().fmap(move |a| f1(a)).transform();
+    let transformed_first = NaturalTransform::<G>::transform(fa).fmap(move |a| f(a));
+    assert_eq!(mapped_first, transformed_first, "transforming does not commute with mapping");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_functor_composition, assert_functor_identity, assert_naturality};
+    use crate::functor::{Covariant, NaturalTransform};
+    use crate::functor::parametric::{Param, ReParam};
+
+    // Keeps only the first element it is mapped over.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Truncating<A>(Vec<A>);
+
+    impl<A> Param for Truncating<A> {
+        type Param = A;
+    }
+
+    impl<A, B> ReParam<B> for Truncating<A> {
+        type Output = Truncating<B>;
+    }
+
+    impl<'a, A, B> Covariant<'a, B> for Truncating<A> {
+        fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> Truncating<B> {
+            Truncating(self.0.into_iter().take(1).map(f).collect())
+        }
+    }
+
+    #[test]
+    fn lawful_instances() {
+        assert_functor_identity(Some(1));
+        assert_functor_identity(Ok::<_, String>(vec![1]));
+        assert_functor_composition(vec![1, 2, 3], |n: i32| n.to_string(), |s: String| s.len());
+        assert_functor_composition(("x", 2), |n: i32| n * 3, |n: i32| Some(n));
+        assert_functor_composition(Truncating(vec![1]), |n: i32| n + 1, |n: i32| n * 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "changed the value")]
+    fn identity_violated() {
+        assert_functor_identity(Truncating(vec![1, 2]));
+    }
+
+    impl<A: Clone> NaturalTransform<Vec<A>> for Truncating<A> {
+        fn transform(self) -> Vec<A> {
+            self.0
+        }
+    }
+
+    #[test]
+    fn natural_transforms() {
+        assert_naturality::<Box<i32>, Option<i32>, _>(Box::new(1), |n| n.to_string());
+        assert_naturality::<Result<i32, ()>, Option<i32>, _>(Err(()), |n| n * 2);
+        assert_naturality::<Vec<u8>, Option<u8>, _>(vec![3, 4], |n| n + 1);
+        assert_naturality::<Truncating<u8>, Vec<u8>, _>(Truncating(vec![3]), |n| n + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not commute")]
+    fn naturality_violated() {
+        assert_naturality::<Truncating<u8>, Vec<u8>, _>(Truncating(vec![3, 4]), |n| n + 1);
+    }
+}
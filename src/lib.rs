@@ -99,19 +99,61 @@
 //!
 //! ... and for every other functor as well. Yay!
 
-extern crate functor;
-extern crate morphism;
+#![cfg_attr(not(feature = "unsafe-morphism"), forbid(unsafe_code))]
+#![cfg_attr(feature = "nightly", feature(fn_traits, unboxed_closures))]
+#![cfg_attr(feature = "allocator-api", feature(allocator_api))]
 
+// Lets the derive macros refer to this crate by name in its own tests.
+#[cfg(all(test, feature = "derive"))]
+extern crate self as coyoneda;
 
-use morphism::Morphism;
-use functor::{Covariant, NaturalTransform};
-use functor::parametric::{Param, ReParam};
+pub mod core;
+pub mod functor;
+pub mod morphism;
+mod trace;
+
+pub mod bicoyoneda;
+pub mod borrowed;
+#[cfg(feature = "sync")]
+pub mod channel;
+pub mod dlist;
+pub mod either;
+pub mod first;
+#[cfg(feature = "futures")]
+pub mod future;
+pub mod iter;
+pub mod laws;
+pub mod monoid;
+pub mod nat;
+pub mod nonempty;
+pub mod pipeline;
+#[cfg(feature = "sync")]
+pub mod shared;
+#[cfg(feature = "futures")]
+pub mod stream;
+#[cfg(feature = "sync")]
+pub mod thread;
+pub mod thunk;
+pub mod trampoline;
+
+#[cfg(feature = "derive")]
+pub use coyoneda_derive::{Functor, NaturalTransform};
+
+use std::ops::Shr;
+
+use crate::morphism::{Morphism, Outlives};
+use crate::functor::{Applicative, Covariant, Monad, NaturalTransform, Traversable};
+use crate::functor::parametric::{Param, ReParam};
+use crate::nat::Nat;
 
 pub struct Coyoneda<'a, T: Param, B> {
     point: T,
     morph: Morphism<'a, T::Param, B>
 }
 
+/// A `Coyoneda` whose closures own everything they capture, for code
+/// that has no use for the lifetime parameter.
+pub type CoyonedaOwned<T, B> = Coyoneda<'static, T, B>;
 
 impl<'a, T: 'a + Param, B: 'a> Coyoneda<'a, T, B> {
 
@@ -121,6 +163,76 @@ impl<'a, T: 'a + Param, B: 'a> Coyoneda<'a, T, B> {
         T::fmap(self.point, move |a| { m.run(a) })
     }
 
+    /// Lower the suspension without consuming it, by cloning the point.
+    pub fn unwrap_cloned<'s>(&'s self) -> <T as ReParam<B>>::Output
+        where T: Clone + Covariant<'s, B> {
+        let m = &self.morph;
+        T::fmap(self.point.clone(), move |a| { m.run(a) })
+    }
+
+    /// Combine two suspensions over an applicative base, by lowering
+    /// both and re-lifting the result.
+    pub fn map2<U, C: 'a, D, F>(self, other: Coyoneda<'a, U, C>, f: F)
+                            -> Coyoneda<'a, <T::Output as ReParam<D>>::Output, D>
+        where T: Covariant<'a, B>, T::Param: 'a, T::Output: Applicative<'a, C, D>,
+              U: 'a + Covariant<'a, C, Output=<T::Output as ReParam<C>>::Output>, U::Param: 'a,
+              F: 'a + Fn(B, C) -> D {
+        From::from(self.unwrap().map2(other.unwrap(), f))
+    }
+
+    /// Sequence a suspension over a monadic base with a function that
+    /// produces another suspension, by lowering, binding and re-lifting.
+    pub fn and_then<U, C: 'a, F>(self, f: F) -> Coyoneda<'a, <T::Output as ReParam<C>>::Output, C>
+        where T: Covariant<'a, B>, T::Param: 'a, T::Output: Monad<'a, C>,
+              U: 'a + Covariant<'a, C, Output=<T::Output as ReParam<C>>::Output>, U::Param: 'a,
+              F: 'a + Fn(B) -> Coyoneda<'a, U, C> {
+        From::from(self.unwrap().bind(move |b| f(b).unwrap()))
+    }
+
+    /// Run a fallible function over the suspension, stopping at the first
+    /// error. On success, the result is lifted into a fresh suspension.
+    pub fn traverse<C: 'a, E, F>(self, f: F) -> Result<Coyoneda<'a, <T as ReParam<C>>::Output, C>, E>
+        where T: Traversable<'a, C>, T::Param: 'a, F: 'a + Fn(B) -> Result<C, E> {
+        let m = self.morph;
+        self.point.traverse(move |a| f(m.run(a))).map(From::from)
+    }
+
+    /// Like `traverse`, for functions that signal failure with `None`.
+    pub fn traverse_option<C: 'a, F>(self, f: F) -> Option<Coyoneda<'a, <T as ReParam<C>>::Output, C>>
+        where T: Traversable<'a, C>, T::Param: 'a, F: 'a + Fn(B) -> Option<C> {
+        let m = self.morph;
+        self.point.traverse_option(move |a| f(m.run(a))).map(From::from)
+    }
+
+    /// An estimate of the heap memory held by the suspended chain of
+    /// functions, in bytes. See `Morphism::approx_heap_bytes`.
+    pub fn approx_heap_bytes(&self) -> usize {
+        self.morph.approx_heap_bytes()
+    }
+
+    /// Like `fmap`, but labels the step with `name` in `tracing` spans,
+    /// and when the morphism is printed with `Debug`.
+    pub fn fmap_named<C: Outlives<'a>, F: Fn(B) -> C + 'a>(self, name: &'static str, f: F) -> Coyoneda<'a, T, C> {
+        Coyoneda{point: self.point, morph: self.morph.tail_named(name, trace::step(Some(name), f))}
+    }
+
+    pub fn natural_transform_to<U>(self) -> Coyoneda<'a, U, B>
+        where T: NaturalTransform<U>, U: Param<Param=T::Param> {
+        let point = self.point;
+        Coyoneda{point: trace::transform(move || point.transform()), morph: self.morph}
+    }
+
+    pub fn unwrap_as<U>(self) -> <U as ReParam<B>>::Output
+        where T: NaturalTransform<U>, U: 'a + Param<Param=T::Param> + Covariant<'a, B> {
+        self.natural_transform_to::<U>().unwrap()
+    }
+
+    pub fn hoist<U, N>(self, n: N) -> Coyoneda<'a, U, B>
+        where U: Param<Param=T::Param>, N: Nat<T, U> {
+        let point = self.point;
+        Coyoneda{point: trace::transform(move || n.apply(point)), morph: self.morph}
+    }
+
 }
 
 impl<'a, T: Param, B> Param for Coyoneda<'a, T, B> {
@@ -131,9 +243,21 @@ impl<'a, T: Param, B, C> ReParam<C> for Coyoneda<'a, T, B> {
     type Output = Coyoneda<'a, T, C>;
 }
 
-impl<'a, T: Param, B, C> Covariant<'a, C> for Coyoneda<'a, T, B> {
+/// Unlike in 0.5, `B` and `C` have to outlive `'a`: without unsafe code,
+/// the chain hands values from one function to the next through cells
+/// that the functions capture.
+impl<'a, T: Param, B: Outlives<'a>, C: Outlives<'a>> Covariant<'a, C> for Coyoneda<'a, T, B> {
     fn fmap<F: Fn(B) -> C + 'a>(self, f: F) -> Coyoneda<'a, T, C> {
-        Coyoneda{point: self.point, morph: self.morph.tail(f)}
+        Coyoneda{point: self.point, morph: self.morph.tail(trace::step(None, f))}
+    }
+}
+
+/// `coyo >> f` is shorthand for `coyo.fmap(f)`.
+impl<'a, T: Param, B: Outlives<'a>, C: Outlives<'a>, F: Fn(B) -> C + 'a> Shr<F> for Coyoneda<'a, T, B> {
+    type Output = Coyoneda<'a, T, C>;
+
+    fn shr(self, f: F) -> Coyoneda<'a, T, C> {
+        self.fmap(f)
     }
 }
 
@@ -146,7 +270,8 @@ impl<'a, T: Param> From<T> for Coyoneda<'a, T, <T as Param>::Param> {
 impl<'a, T, U, B> NaturalTransform<Coyoneda<'a, U, B>> for Coyoneda<'a, T, B>
     where T: Param + NaturalTransform<U>, U: Param<Param=T::Param> {
     fn transform(self) -> Coyoneda<'a, U, B> {
-        Coyoneda{point: self.point.transform(), morph: self.morph}
+        let point = self.point;
+        Coyoneda{point: trace::transform(move || point.transform()), morph: self.morph}
     }
 }
 
@@ -154,8 +279,27 @@ mod test {
 #![cfg(test)]
 
     use super::*;
-    use functor::{Covariant, NaturalTransform};
-    use functor::parametric::Param;
+    use std::borrow::Cow;
+    #[cfg(feature = "std-collections")]
+    use std::cmp::Reverse;
+    use std::cell::{Cell, RefCell};
+    use std::iter;
+    use std::marker::PhantomData;
+    use std::ops::{Bound, ControlFlow};
+    use std::rc::Rc;
+    #[cfg(feature = "sync")]
+    use std::sync::{Arc, Mutex, RwLock};
+    use std::task::Poll;
+    #[cfg(feature = "sync")]
+    use std::thread;
+    #[cfg(feature = "std-collections")]
+    use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+    use crate::functor::{Bifunctor, Covariant, NaturalTransform};
+    use crate::functor::parametric::{Param, ReParam};
+    use crate::either::Either;
+    use crate::nat::{Nat, BoxToOk, BoxToRc, Identity, ResultToEither, Transform, compose};
+    #[cfg(feature = "sync")]
+    use crate::nat::BoxToArc;
 
     fn add_and_to_string<T: Param>(y: Coyoneda<T, i32>) -> Coyoneda<T, String> {
         y.fmap(|n: i32| n + 1)
@@ -185,6 +329,385 @@ mod test {
         assert_eq!(y.unwrap(), Ok("43foobar".to_string()))
     }
 
+    #[test]
+    fn unwrap_cloned_twice() {
+        let x: Result<i32, ()> = Ok(42);
+        let y = add_and_to_string(From::from(x));
+        assert_eq!(y.unwrap_cloned(), Ok("43foobar".to_string()));
+        assert_eq!(y.unwrap_cloned(), Ok("43foobar".to_string()))
+    }
+
+    #[test]
+    fn map2_option() {
+        let x = add_and_to_string(From::from(Some(42)));
+        let y = Coyoneda::from(Some(3));
+        let z = x.map2(y, |s, n| s.repeat(n)).fmap(|s| s.len());
+        assert_eq!(z.unwrap(), Some(24))
+    }
+
+    #[test]
+    fn map2_result() {
+        let x: Coyoneda<Result<i32, &str>, i32> = From::from(Ok(1));
+        let y: Coyoneda<Result<i32, &str>, i32> = From::from(Err("nope"));
+        assert_eq!(x.map2(y, |a, b| a + b).unwrap(), Err("nope"))
+    }
+
+    #[test]
+    fn and_then_option() {
+        let x = Coyoneda::from(Some(42)).fmap(|n: i32| n + 1);
+        let y = x.and_then(|n| Coyoneda::from(if n > 0 { Some(n) } else { None }).fmap(|n: i32| n.to_string()));
+        let z = y.fmap(|s| s + "foo").and_then(|_| Coyoneda::from(None::<()>));
+        assert_eq!(z.unwrap(), None)
+    }
+
+    #[test]
+    fn and_then_result() {
+        let x: Coyoneda<Result<i32, String>, i32> = From::from(Ok(42));
+        let y = add_and_to_string(x).and_then(|s| Coyoneda::from(s.parse::<i32>().map_err(|e| e.to_string())));
+        assert_eq!(y.unwrap(), Err("invalid digit found in string".to_string()))
+    }
+
+    #[test]
+    fn traverse_result() {
+        let x = add_and_to_string(From::from(Some(42)));
+        let y = x.traverse(|s| if s.len() > 3 { Ok(s.len()) } else { Err(s) });
+        assert_eq!(y.map(|y| y.fmap(|n| n * 2).unwrap()), Ok(Some(16)));
+        let x = add_and_to_string(From::from(Box::new(42)));
+        let y = x.traverse(|s| s.parse::<i32>().map_err(|_| s));
+        assert_eq!(y.err(), Some("43foobar".to_string()))
+    }
+
+    #[test]
+    fn traverse_option() {
+        let x: Coyoneda<Result<i32, ()>, String> = add_and_to_string(From::from(Ok(42)));
+        let y = x.traverse_option(|s| s.chars().next());
+        assert_eq!(y.map(|y| y.unwrap()), Some(Ok('4')))
+    }
+
+    #[test]
+    fn fmap_named() {
+        let x = Coyoneda::from(Some(42))
+            .fmap_named("increment", |n: i32| n + 1)
+            .fmap_named("stringify", |n| n.to_string());
+        assert_eq!(x.unwrap(), Some("43".to_string()))
+    }
+
+    #[test]
+    fn fmap_owned() {
+        fn add_and_to_string_owned<T: 'static + Param<Param=i32>>(y: T) -> CoyonedaOwned<T, String> {
+            let suffix = "foo".to_string();
+            CoyonedaOwned::from(y).fmap(|n: i32| n + 1).fmap(move |n| n.to_string() + &suffix)
+        }
+        assert_eq!(add_and_to_string_owned(Some(42)).unwrap(), Some("43foo".to_string()))
+    }
+
+    #[test]
+    fn fmap_vec() {
+        let x = vec![1, 2, 3];
+        let y = add_and_to_string(From::from(x));
+        assert_eq!(y.unwrap(), vec!["2foobar", "3foobar", "4foobar"])
+    }
+
+    #[test]
+    fn fmap_boxed_slice() {
+        let x: Box<[i32]> = vec![1, 2, 3].into_boxed_slice();
+        let y = add_and_to_string(From::from(x)).unwrap();
+        assert_eq!(&*y, ["2foobar", "3foobar", "4foobar"])
+    }
+
+    #[cfg(feature = "std-collections")]
+    #[test]
+    fn fmap_vec_deque() {
+        let mut x = VecDeque::new();
+        x.push_back(1);
+        x.push_front(0);
+        let y = add_and_to_string(From::from(x));
+        assert_eq!(y.unwrap(), vec!["1foobar", "2foobar"])
+    }
+
+    #[cfg(feature = "std-collections")]
+    #[test]
+    fn fmap_linked_list() {
+        let x: LinkedList<i32> = vec![1, 2].into_iter().collect();
+        let y = add_and_to_string(From::from(x));
+        assert_eq!(y.unwrap().into_iter().collect::<Vec<_>>(), vec!["2foobar", "3foobar"])
+    }
+
+    #[cfg(feature = "std-collections")]
+    #[test]
+    fn fmap_hash_map() {
+        let mut x = HashMap::new();
+        x.insert("a", 1);
+        x.insert("b", 2);
+        let y = add_and_to_string(From::from(x)).unwrap();
+        assert_eq!(y.len(), 2);
+        assert_eq!(y["a"], "2foobar");
+        assert_eq!(y["b"], "3foobar")
+    }
+
+    #[cfg(feature = "std-collections")]
+    #[test]
+    fn fmap_btree_map() {
+        let mut x = BTreeMap::new();
+        x.insert(2, 20);
+        x.insert(1, 10);
+        let y = add_and_to_string(From::from(x)).unwrap();
+        assert_eq!(y.into_iter().collect::<Vec<_>>(),
+                   vec![(1, "11foobar".to_string()), (2, "21foobar".to_string())])
+    }
+
+    #[cfg(feature = "std-collections")]
+    #[test]
+    fn fmap_sets() {
+        let x: HashSet<i32> = vec![1, 2, 3].into_iter().collect();
+        let y = Coyoneda::from(x).fmap(|n: i32| n % 2).unwrap();
+        assert_eq!(y, vec![0, 1].into_iter().collect());
+        let x: BTreeSet<i32> = vec![3, 1, 2].into_iter().collect();
+        let y = add_and_to_string(From::from(x)).unwrap();
+        assert_eq!(y.into_iter().collect::<Vec<_>>(), vec!["2foobar", "3foobar", "4foobar"])
+    }
+
+    #[cfg(feature = "std-collections")]
+    #[test]
+    fn fmap_binary_heap() {
+        let x: BinaryHeap<i32> = vec![1, 3, 2].into_iter().collect();
+        let y = Coyoneda::from(x).fmap(|n: i32| Reverse(n * 10)).unwrap();
+        assert_eq!(y.into_sorted_vec(), vec![Reverse(30), Reverse(20), Reverse(10)])
+    }
+
+    #[test]
+    fn fmap_phantom_data() {
+        let x: PhantomData<i32> = PhantomData;
+        let y: PhantomData<String> = add_and_to_string(From::from(x)).unwrap();
+        assert_eq!(y, PhantomData)
+    }
+
+    #[test]
+    fn fmap_iter_option_and_empty() {
+        let x = Some(1).into_iter();
+        let y = add_and_to_string(From::from(x)).unwrap();
+        assert_eq!(y.collect::<Vec<_>>(), vec!["2foobar"]);
+        let x = iter::empty::<i32>();
+        let y = add_and_to_string(From::from(x)).unwrap();
+        assert_eq!(y.count(), 0)
+    }
+
+    #[test]
+    fn approx_heap_bytes() {
+        let x = Coyoneda::from(Some(1));
+        let before = x.approx_heap_bytes();
+        let y = x.fmap(|n: i32| n + 1);
+        assert!(y.approx_heap_bytes() > before);
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derive_functor() {
+        use crate::functor::gat::Functor;
+
+        #[derive(Debug, PartialEq, crate::Functor)]
+        enum Tree<A> {
+            Leaf(A),
+            Node(Vec<Tree<A>>),
+        }
+
+        #[derive(Debug, PartialEq, crate::Functor)]
+        struct Labelled<'l, L, A> {
+            label: &'l str,
+            extra: L,
+            pair: (A, Option<A>),
+            tree: Box<Tree<A>>,
+        }
+
+        let t = Tree::Node(vec![Tree::Leaf(1), Tree::Node(vec![Tree::Leaf(2)])]);
+        let x = Labelled { label: "x", extra: 'c', pair: (3, None), tree: Box::new(t) };
+        let y = add_and_to_string(Coyoneda::from(x)).unwrap();
+        assert_eq!(y.pair, ("4foobar".to_string(), None));
+        assert_eq!(Functor::fmap(*y.tree, |s| s.len()), Tree::Node(vec![
+            Tree::Leaf(7),
+            Tree::Node(vec![Tree::Leaf(7)]),
+        ]));
+        assert_eq!((y.label, y.extra), ("x", 'c'));
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derive_natural_transform() {
+        #[derive(Debug, PartialEq, crate::Functor)]
+        enum Console<A> {
+            Print(String, A),
+            Prompt { text: String, next: A },
+            Exit,
+        }
+
+        #[derive(Debug, PartialEq, crate::Functor)]
+        struct Logged<A>(A);
+
+        #[derive(crate::Functor, crate::NaturalTransform)]
+        #[natural_transform(Console<A>)]
+        #[natural_transform(into = Logged<A>)]
+        enum Io<A> {
+            Print(&'static str, A),
+            Prompt { text: &'static str, next: A },
+            Exit,
+        }
+
+        impl<A> From<Io<A>> for Logged<A> {
+            fn from(io: Io<A>) -> Logged<A> {
+                match io {
+                    Io::Print(_, a) | Io::Prompt { next: a, .. } => Logged(a),
+                    Io::Exit => panic!("nothing to log"),
+                }
+            }
+        }
+
+        let x = add_and_to_string(Coyoneda::from(Io::Print("hi", 1)));
+        assert_eq!(x.unwrap_as::<Console<_>>(), Console::Print("hi".to_string(), "2foobar".to_string()));
+        let x = Coyoneda::from(Io::Prompt { text: "?", next: 2 }).fmap(|n: i32| n * 2);
+        assert_eq!(x.natural_transform_to::<Console<_>>().unwrap(), Console::Prompt { text: "?".to_string(), next: 4 });
+        assert_eq!(Coyoneda::from(Io::Exit::<i32>).unwrap_as::<Console<_>>(), Console::Exit);
+        assert_eq!(Coyoneda::from(Io::Print("", 3)).unwrap_as::<Logged<_>>(), Logged(3));
+    }
+
+    #[test]
+    fn fmap_shr() {
+        let y = Coyoneda::from(Some(42)) >> (|n: i32| n + 1) >> (|n: i32| n.to_string());
+        assert_eq!(y.unwrap(), Some("43".to_string()))
+    }
+
+    #[test]
+    fn fmap_array() {
+        let x = [1, 2, 3];
+        let y = add_and_to_string(From::from(x));
+        assert_eq!(y.unwrap(), ["2foobar", "3foobar", "4foobar"])
+    }
+
+    #[test]
+    fn fmap_tuple() {
+        let x = ("log", 42);
+        let y = add_and_to_string(From::from(x));
+        assert_eq!(y.unwrap(), ("log", "43foobar".to_string()));
+        let x = ('a', "log", 1.5, 42);
+        let y = add_and_to_string(From::from(x));
+        assert_eq!(y.unwrap(), ('a', "log", 1.5, "43foobar".to_string()))
+    }
+
+    #[test]
+    fn fmap_rc() {
+        let x = Rc::new(42);
+        let y = add_and_to_string(From::from(x.clone()));
+        assert_eq!(y.unwrap(), Rc::new("43foobar".to_string()));
+        assert_eq!(*x, 42)
+    }
+
+    #[test]
+    fn fmap_rc_slice() {
+        let x: Rc<[i32]> = Rc::from(vec![1, 2]);
+        let y = add_and_to_string(From::from(x.clone()));
+        assert_eq!(&*y.unwrap(), ["2foobar", "3foobar"]);
+        assert_eq!(&*x, [1, 2])
+    }
+
+    #[test]
+    fn hoist_box_to_rc_to_box() {
+        let x = add_and_to_string(From::from(Box::new(42))).hoist(BoxToRc);
+        assert_eq!(x.unwrap_as::<Box<_>>(), Box::new("43foobar".to_string()))
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn fmap_arc() {
+        let x = Arc::new(42);
+        let y = add_and_to_string(From::from(x.clone()));
+        assert_eq!(y.unwrap(), Arc::new("43foobar".to_string()));
+        assert_eq!(*x, 42)
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn hoist_box_to_arc_to_box() {
+        let x = add_and_to_string(From::from(Box::new(42))).hoist(BoxToArc);
+        assert_eq!(x.unwrap_as::<Box<_>>(), Box::new("43foobar".to_string()))
+    }
+
+    #[test]
+    fn fmap_cow() {
+        let x = 42;
+        let y = add_and_to_string(From::from(Cow::Borrowed(&x))).fmap(|s| s.len());
+        match y.unwrap() {
+            Cow::Owned(n) => assert_eq!(n, 8),
+            Cow::Borrowed(_) => panic!("expected an owned value")
+        }
+    }
+
+    #[test]
+    fn fmap_cells() {
+        let x = add_and_to_string(From::from(Cell::new(42)));
+        assert_eq!(x.unwrap().into_inner(), "43foobar");
+        let x = RefCell::new(42);
+        *x.borrow_mut() += 1;
+        let y = add_and_to_string(From::from(x));
+        assert_eq!(y.unwrap().into_inner(), "44foobar")
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn fmap_locks() {
+        let x = add_and_to_string(From::from(Mutex::new(42)));
+        assert_eq!(x.unwrap().into_inner().unwrap(), "43foobar");
+        let x = add_and_to_string(From::from(RwLock::new(42)));
+        assert_eq!(x.unwrap().into_inner().unwrap(), "43foobar")
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn fmap_poisoned_mutex() {
+        let x = Arc::new(Mutex::new(42));
+        let y = x.clone();
+        let _ = thread::spawn(move || {
+            let _guard = y.lock().unwrap();
+            panic!("poison");
+        }).join();
+        assert!(x.is_poisoned());
+        let x = Arc::try_unwrap(x).unwrap();
+        let z = add_and_to_string(From::from(x)).unwrap();
+        assert!(!z.is_poisoned());
+        assert_eq!(z.into_inner().unwrap(), "43foobar")
+    }
+
+    #[test]
+    fn fmap_poll() {
+        let x = add_and_to_string(From::from(Poll::Ready(42)));
+        assert_eq!(x.unwrap(), Poll::Ready("43foobar".to_string()));
+        let x = add_and_to_string(From::from(Poll::Pending));
+        assert_eq!(x.unwrap(), Poll::Pending)
+    }
+
+    #[test]
+    fn fmap_control_flow() {
+        let x: ControlFlow<&str, i32> = ControlFlow::Continue(42);
+        let y = add_and_to_string(From::from(x));
+        assert_eq!(y.unwrap(), ControlFlow::Continue("43foobar".to_string()));
+        let x: ControlFlow<&str, i32> = ControlFlow::Break("done");
+        assert_eq!(x.bimap(|s| s.len(), |n| n + 1), ControlFlow::Break(4))
+    }
+
+    #[test]
+    fn fmap_bound() {
+        let x = add_and_to_string(From::from(Bound::Excluded(42)));
+        assert_eq!(x.unwrap(), Bound::Excluded("43foobar".to_string()));
+        let x = add_and_to_string(From::from(Bound::Unbounded));
+        assert_eq!(x.unwrap(), Bound::Unbounded)
+    }
+
+    #[test]
+    fn fmap_function() {
+        let x: Box<dyn Fn(i32) -> i32> = Box::new(|n| n * 2);
+        let y = add_and_to_string(From::from(x)).unwrap();
+        assert_eq!(y(21), "43foobar");
+        assert_eq!(y(0), "1foobar")
+    }
+
     #[test]
     fn natural_transform_box_to_option() {
         let x = Box::new(42);
@@ -201,4 +724,126 @@ mod test {
         assert_eq!(z.unwrap(), Some("43foobar".to_string()))
     }
 
+    #[test]
+    fn natural_transform_option_to_vec_to_option() {
+        let x = Some(42);
+        let y = add_and_to_string(From::from(x)).natural_transform_to::<Vec<_>>();
+        assert_eq!(y.unwrap_as::<Option<_>>(), Some("43foobar".to_string()))
+    }
+
+    #[test]
+    fn natural_transform_option_to_result() {
+        let x: Option<i32> = None;
+        let y = add_and_to_string(From::from(x));
+        assert_eq!(y.unwrap_as::<Result<_, ()>>(), Err(()))
+    }
+
+    #[test]
+    fn hoist_result_to_either() {
+        let x: Result<i32, &str> = Ok(42);
+        let y = add_and_to_string(From::from(x));
+        let z = y.hoist(ResultToEither);
+        assert_eq!(z.unwrap(), Either::Right("43foobar".to_string()))
+    }
+
+    #[test]
+    fn hoist_box_to_ok() {
+        let x = Box::new(42);
+        let y = add_and_to_string(From::from(x));
+        let z = y.hoist(BoxToOk);
+        assert_eq!(z.unwrap(), Ok::<_, ()>("43foobar".to_string()))
+    }
+
+    struct BoxToOption;
+
+    impl<A> Nat<Box<A>, Option<A>> for BoxToOption {
+        fn apply(&self, x: Box<A>) -> Option<A> { Some(*x) }
+    }
+
+    #[test]
+    fn hoist_box_to_option() {
+        let x = add_and_to_string(From::from(Box::new(42)));
+        let y = add_and_to_string(From::from(Box::new(1)));
+        assert_eq!(x.hoist(BoxToOption).unwrap(), Some("43foobar".to_string()));
+        assert_eq!(y.hoist(BoxToOption).unwrap(), Some("2foobar".to_string()))
+    }
+
+    #[test]
+    fn hoist_closure() {
+        let x: Result<i32, ()> = Err(());
+        let y = add_and_to_string(From::from(x));
+        let z = y.hoist(|r: Result<i32, ()>| r.ok());
+        assert_eq!(z.unwrap(), None)
+    }
+
+    #[test]
+    fn hoist_composed() {
+        let x: Result<i32, ()> = Ok(42);
+        let y = add_and_to_string(From::from(x));
+        let n = compose::<_, _, Result<i32, ()>>(Identity, Transform)
+            .then(|o: Option<i32>| o.filter(|n| *n > 0));
+        let z = y.hoist(n);
+        assert_eq!(z.unwrap(), Some("43foobar".to_string()))
+    }
+
+    #[test]
+    fn composed_through_rc_is_send() {
+        fn assert_send_sync<T: Send + Sync>(_: &T) {}
+        let n = compose::<_, _, Rc<i32>>(BoxToRc, |x: Rc<i32>| Box::new(*x));
+        assert_send_sync(&n);
+        assert_eq!(n.apply(Box::new(1)), Box::new(1))
+    }
+
+    trait Source {
+        fn values(&self) -> Vec<i32>;
+    }
+
+    impl Param for Box<dyn Source> {
+        type Param = i32;
+    }
+
+    impl<B> ReParam<B> for Box<dyn Source> {
+        type Output = Vec<B>;
+    }
+
+    impl<'a, B> Covariant<'a, B> for Box<dyn Source> {
+        fn fmap<F: Fn(i32) -> B + 'a>(self, f: F) -> Vec<B> {
+            self.values().into_iter().map(f).collect()
+        }
+    }
+
+    impl<'r> Param for dyn Source + 'r {
+        type Param = i32;
+    }
+
+    struct Range(i32);
+
+    impl Source for Range {
+        fn values(&self) -> Vec<i32> { (0..self.0).collect() }
+    }
+
+    #[test]
+    fn fmap_trait_object() {
+        let x: Box<dyn Source> = Box::new(Range(3));
+        let y = add_and_to_string(From::from(x));
+        assert_eq!(y.unwrap(), vec!["1foobar", "2foobar", "3foobar"])
+    }
+
+    impl<B> ReParam<B> for &dyn Source {
+        type Output = Vec<B>;
+    }
+
+    impl<'a, B> Covariant<'a, B> for &dyn Source {
+        fn fmap<F: Fn(i32) -> B + 'a>(self, f: F) -> Vec<B> {
+            self.values().into_iter().map(f).collect()
+        }
+    }
+
+    #[test]
+    fn fmap_trait_object_reference() {
+        let x = Range(3);
+        let y = add_and_to_string(From::from(&x as &dyn Source));
+        assert_eq!(y.unwrap(), vec!["1foobar", "2foobar", "3foobar"])
+    }
+
 }
@@ -0,0 +1,80 @@
+//! Types whose values can be combined.
+
+/// Types with an associative operation for combining two values.
+pub trait Semigroup {
+    fn combine(self, other: Self) -> Self;
+}
+
+/// Semigroups with a value that combines with any other to give back
+/// the other one.
+pub trait Monoid: Semigroup + Sized {
+    fn empty() -> Self;
+
+    /// Combine all values, from left to right.
+    fn concat<I: IntoIterator<Item=Self>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::empty(), Self::combine)
+    }
+}
+
+impl Semigroup for () {
+    fn combine(self, _: ()) {}
+}
+
+impl Monoid for () {
+    fn empty() {}
+}
+
+impl Semigroup for String {
+    fn combine(self, other: String) -> String {
+        self + &other
+    }
+}
+
+impl Monoid for String {
+    fn empty() -> String {
+        String::new()
+    }
+}
+
+impl<A> Semigroup for Vec<A> {
+    fn combine(mut self, mut other: Vec<A>) -> Vec<A> {
+        self.append(&mut other);
+        self
+    }
+}
+
+impl<A> Monoid for Vec<A> {
+    fn empty() -> Vec<A> {
+        Vec::new()
+    }
+}
+
+/// Combines the values inside, if both are present.
+impl<S: Semigroup> Semigroup for Option<S> {
+    fn combine(self, other: Option<S>) -> Option<S> {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.combine(b)),
+            (a, None) => a,
+            (None, b) => b
+        }
+    }
+}
+
+impl<S: Semigroup> Monoid for Option<S> {
+    fn empty() -> Option<S> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Monoid, Semigroup};
+
+    #[test]
+    fn concat() {
+        assert_eq!(String::concat(vec!["a".to_string(), "b".to_string()]), "ab");
+        assert_eq!(Vec::concat(vec![vec![1], vec![], vec![2, 3]]), vec![1, 2, 3]);
+        assert_eq!(Some(vec![1]).combine(None).combine(Some(vec![2])), Some(vec![1, 2]));
+        assert_eq!(Option::<String>::concat(None), None);
+    }
+}
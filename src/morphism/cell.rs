@@ -0,0 +1,116 @@
+//! The safe path: every point between two steps of the chain is a
+//! shared cell. Each step takes its argument out of the cell before it,
+//! and puts its result into the cell after it.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use super::{Chain, Label};
+
+pub type Slot = ();
+
+// Nothing needs to be kept between runs: the points are allocated along
+// with the chain.
+pub type Scratch = ();
+
+type Point<A> = Rc<Cell<Option<A>>>;
+
+pub struct Ends<A, B> {
+    input: Point<A>,
+    output: Point<B>,
+}
+
+// Clones share their points, which is fine as long as runs of either
+// one leave every point empty again.
+impl<A, B> Clone for Ends<A, B> {
+    fn clone(&self) -> Ends<A, B> {
+        Ends {
+            input: self.input.clone(),
+            output: self.output.clone(),
+        }
+    }
+}
+
+impl<A> Ends<A, A> {
+    #[inline(always)]
+    pub fn identity() -> Ends<A, A> {
+        let point = Rc::new(Cell::new(None));
+        Ends {
+            input: point.clone(),
+            output: point,
+        }
+    }
+}
+
+impl<A, B> Ends<A, B> {
+    #[inline(always)]
+    pub fn head<'a, Z, F>(self, chain: &mut Chain<'a>, label: Label, f: F) -> Ends<Z, B>
+        where F: Fn(Z) -> A + 'a, A: 'a, Z: 'a,
+    {
+        let input = Rc::new(Cell::new(None));
+        let (src, dst) = (input.clone(), self.input);
+        chain.push_head(label, move |()| dst.set(src.take().map(&f)));
+        Ends {
+            input,
+            output: self.output,
+        }
+    }
+
+    #[inline(always)]
+    pub fn tail<'a, C, F>(self, chain: &mut Chain<'a>, label: Label, f: F) -> Ends<A, C>
+        where F: Fn(B) -> C + 'a, B: 'a, C: 'a,
+    {
+        let output = Rc::new(Cell::new(None));
+        let (src, dst) = (self.output, output.clone());
+        chain.push_tail(label, move |()| dst.set(src.take().map(&f)));
+        Ends {
+            input: self.input,
+            output,
+        }
+    }
+
+    #[inline(always)]
+    pub fn push_front<'a, F>(&mut self, chain: &mut Chain<'a>, f: F)
+        where F: Fn(A) -> A + 'a, A: 'a,
+    {
+        let point = self.input.clone();
+        chain.front.push(chain.step(move |()| point.set(point.take().map(&f))));
+    }
+
+    #[inline(always)]
+    pub fn push_back<'a, F>(&mut self, chain: &mut Chain<'a>, f: F)
+        where F: Fn(B) -> B + 'a, B: 'a,
+    {
+        let point = self.output.clone();
+        chain.back.push(chain.step(move |()| point.set(point.take().map(&f))));
+    }
+
+    #[inline(always)]
+    pub fn then<'a, C>(self, chain: &mut Chain<'a>, other: Ends<B, C>) -> Ends<A, C>
+        where B: 'a,
+    {
+        let (src, dst) = (self.output, other.input);
+        chain.push_join(move |()| dst.set(src.take()));
+        Ends {
+            input: self.input,
+            output: other.output,
+        }
+    }
+
+    #[inline(always)]
+    pub fn run_with(&self, chain: &Chain<'_>, _: &mut Scratch, x: A) -> B {
+        self.run(chain, x)
+    }
+
+    #[inline(always)]
+    pub fn run(&self, chain: &Chain<'_>, x: A) -> B {
+        self.input.set(Some(x));
+        for f in chain.steps() {
+            f(());
+        }
+        match self.output.take() {
+            Some(y) => y,
+            None => unreachable!()
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Endo-chains as a monoid.
+
+use crate::monoid::{Monoid, Semigroup};
+
+use super::Morphism;
+
+/// A `Morphism` from a type to itself, which forms a monoid under
+/// composition: `empty` is the identity chain, and `combine` runs one
+/// chain after the other.
+///
+/// # Example
+///
+/// ```rust
+/// use coyoneda::monoid::Monoid;
+/// use coyoneda::morphism::Endo;
+///
+/// let f = Endo::concat((1..=3u64).map(|i| Endo::from_fn(move |x| x * 10 + i)));
+/// assert_eq!(f.run(0), 123);
+/// ```
+pub struct Endo<'a, A>(pub Morphism<'a, A>);
+
+impl<'a, A: 'a> Endo<'a, A> {
+    /// Wrap a single closure.
+    pub fn from_fn<F: Fn(A) -> A + 'a>(f: F) -> Endo<'a, A> {
+        Endo(Morphism::new().tail(f))
+    }
+
+    /// Run the chain on an argument.
+    pub fn run(&self, x: A) -> A {
+        self.0.run(x)
+    }
+
+    pub fn into_inner(self) -> Morphism<'a, A> {
+        self.0
+    }
+}
+
+impl<'a, A> Default for Endo<'a, A> {
+    fn default() -> Endo<'a, A> {
+        Endo(Morphism::new())
+    }
+}
+
+impl<'a, A> Clone for Endo<'a, A> {
+    fn clone(&self) -> Endo<'a, A> {
+        Endo(self.0.clone())
+    }
+}
+
+impl<'a, A> From<Morphism<'a, A>> for Endo<'a, A> {
+    fn from(f: Morphism<'a, A>) -> Endo<'a, A> {
+        Endo(f)
+    }
+}
+
+impl<'a, A: 'a> Semigroup for Endo<'a, A> {
+    fn combine(self, other: Endo<'a, A>) -> Endo<'a, A> {
+        Endo(self.0.then(other.0))
+    }
+}
+
+impl<'a, A: 'a> Monoid for Endo<'a, A> {
+    fn empty() -> Endo<'a, A> {
+        Endo::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Endo;
+    use crate::monoid::{Monoid, Semigroup};
+
+    #[test]
+    fn fold_endos() {
+        let steps = ["trim", "upper", "trim"];
+        let f = steps.iter().map(|&s| match s {
+            "trim" => Endo::from_fn(|s: String| s.trim().to_string()),
+            _ => Endo::from_fn(|s: String| s.to_uppercase()),
+        }).fold(Endo::default(), |a, b| a.combine(b));
+        assert_eq!(f.run(" abc ".to_string()), "ABC");
+        assert_eq!(f.clone().combine(Endo::empty()).into_inner().len(), 3);
+        assert_eq!(Endo::<u8>::concat(Vec::new()).run(7), 7);
+    }
+}
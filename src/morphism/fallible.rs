@@ -0,0 +1,135 @@
+//! Chains of fallible closures.
+
+use super::Void;
+use super::points::{Point, Points};
+
+type Step<'a, E> = Box<dyn Fn() -> Result<(), E> + 'a>;
+
+/// A suspended chain of fallible closures that behave as a function
+/// from `A` to `Result<B, E>`.
+///
+/// Running the chain stops at the first closure that returns an error,
+/// so the closures after it are never called.
+pub struct TryMorphism<'a, A, B, E> {
+    // A step that fails leaves the point after it empty.
+    chain: Points<Step<'a, E>, A, B>,
+}
+
+impl TryMorphism<'static, Void, Void, Void> {
+    /// Create the identity chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::TryMorphism;
+    ///
+    /// let f = TryMorphism::new::<u64, ()>();
+    /// assert_eq!(f.run(42u64), Ok(42u64));
+    /// ```
+    #[inline]
+    pub fn new<'a, A, E>() -> TryMorphism<'a, A, A, E> {
+        TryMorphism {
+            chain: Points::new(),
+        }
+    }
+}
+
+impl<'a, B: 'a, C, E> TryMorphism<'a, B, C, E> {
+    /// Attach a fallible closure to the front of the chain
+    /// (pre-composition).
+    #[inline]
+    pub fn head<A: 'a, F>(self, f: F) -> TryMorphism<'a, A, C, E>
+        where F: Fn(A) -> Result<B, E> + 'a,
+    {
+        TryMorphism {
+            chain: self.chain.head(|src, dst| Box::new(move || step(&src, &dst, &f))),
+        }
+    }
+}
+
+impl<'a, A, B: 'a, E> TryMorphism<'a, A, B, E> {
+    /// Attach a fallible closure to the back of the chain
+    /// (post-composition).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::TryMorphism;
+    ///
+    /// let f = TryMorphism::new::<&str, _>()
+    ///     .tail(|s| s.parse::<u8>().map_err(|e| e.to_string()))
+    ///     .tail(|x| x.checked_mul(2).ok_or("overflow".to_string()));
+    /// assert_eq!(f.run("21"), Ok(42));
+    /// assert_eq!(f.run("200"), Err("overflow".to_string()));
+    /// ```
+    #[inline]
+    pub fn tail<C: 'a, F>(self, f: F) -> TryMorphism<'a, A, C, E>
+        where F: Fn(B) -> Result<C, E> + 'a,
+    {
+        TryMorphism {
+            chain: self.chain.tail(|src, dst| Box::new(move || step(&src, &dst, &f))),
+        }
+    }
+
+    /// Compose one `TryMorphism` with another.
+    #[inline]
+    pub fn then<C: 'a>(self, other: TryMorphism<'a, B, C, E>) -> TryMorphism<'a, A, C, E> {
+        TryMorphism {
+            chain: self.chain.then(other.chain, |src, dst| Box::new(move || { dst.set(src.take()); Ok(()) })),
+        }
+    }
+
+    /// Given an argument, run the chain of closures in a loop until one
+    /// of them fails, and return the final result or the first error.
+    #[inline]
+    pub fn run(&self, x: A) -> Result<B, E> {
+        self.chain.put(x);
+        for f in self.chain.steps() {
+            f()?;
+        }
+        Ok(self.chain.take())
+    }
+}
+
+#[inline(always)]
+fn step<X, Y, E, F>(src: &Point<X>, dst: &Point<Y>, f: &F) -> Result<(), E>
+    where F: Fn(X) -> Result<Y, E>,
+{
+    match src.take() {
+        Some(x) => f(x).map(|y| dst.set(Some(y))),
+        None => unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::TryMorphism;
+
+    #[test]
+    fn short_circuit() {
+        let calls = Cell::new(0);
+        let f = TryMorphism::new::<i32, String>()
+            .tail(|x| if x >= 0 { Ok(x as u32) } else { Err(format!("{} < 0", x)) })
+            .tail(|x| { calls.set(calls.get() + 1); Ok(x + 1) })
+            .head(|s: &str| s.parse().map_err(|_| format!("not a number: {}", s)));
+        assert_eq!(f.run("41"), Ok(42));
+        assert_eq!(f.run("-1"), Err("-1 < 0".to_string()));
+        assert_eq!(f.run("x"), Err("not a number: x".to_string()));
+        assert_eq!(calls.get(), 1);
+        let g = TryMorphism::new::<u32, String>().tail(|x| Ok(x * 2));
+        assert_eq!(f.then(g).run("20"), Ok(42));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn long_chain() {
+        let mut f = TryMorphism::new::<u64, u64>().tail(Ok);
+        for _ in 0..100000 {
+            f = f.tail(|x| if x < 50000 { Ok(x + 1) } else { Err(x) });
+        }
+        assert_eq!(f.run(0), Err(50000));
+        assert_eq!(f.run(60000), Err(60000));
+    }
+}
@@ -0,0 +1,154 @@
+//! Chains of function pointers that live on the stack.
+
+use crate::either::Either;
+
+use super::Morphism;
+
+fn identity<A>(x: A) -> A {
+    x
+}
+
+/// A chain of at most `N` function pointers from a type to itself,
+/// stored inline so that building and running it never allocates.
+///
+/// Plain `fn` pointers from one type to another can't be kept in a
+/// single array without unsafe code, so unlike `Morphism` the steps of
+/// a `ConstMorphism` must all have the type `fn(A) -> A`.
+///
+/// Once the chain is full, `tail` moves it into a `Morphism` instead.
+///
+/// # Example
+///
+/// ```rust
+/// use coyoneda::either::Either;
+/// use coyoneda::morphism::ConstMorphism;
+///
+/// fn inc(x: u64) -> u64 { x + 1 }
+/// fn double(x: u64) -> u64 { x * 2 }
+///
+/// const F: ConstMorphism<u64, 1> = ConstMorphism::new();
+/// let f = match F.tail(double) {
+///     Either::Left(f) => f,
+///     Either::Right(_) => unreachable!(),
+/// };
+/// assert_eq!(f.run(21), 42);
+/// match f.tail(inc) {
+///     Either::Left(_) => unreachable!(),
+///     Either::Right(g) => assert_eq!(g.run(21), 43),
+/// }
+/// ```
+pub struct ConstMorphism<A, const N: usize> {
+    steps: [fn(A) -> A; N],
+    len: usize,
+}
+
+impl<A, const N: usize> ConstMorphism<A, N> {
+    /// Create the identity chain, with room for `N` steps.
+    pub const fn new() -> ConstMorphism<A, N> {
+        ConstMorphism {
+            steps: [identity::<A> as fn(A) -> A; N],
+            len: 0,
+        }
+    }
+
+    /// The number of steps in the chain.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Attach a step to the back of the chain.
+    ///
+    /// Returns the chain in `Left` while there is room for `f`, and
+    /// otherwise moves it, along with `f`, into a `Morphism` in `Right`.
+    pub fn tail(mut self, f: fn(A) -> A) -> Either<ConstMorphism<A, N>, Morphism<'static, A>>
+        where A: 'static,
+    {
+        if self.len < N {
+            self.steps[self.len] = f;
+            self.len += 1;
+            Either::Left(self)
+        } else {
+            Either::Right(self.into_morphism().tail(f))
+        }
+    }
+
+    /// Run the chain on an argument.
+    pub fn run(&self, mut x: A) -> A {
+        for f in &self.steps[..self.len] {
+            x = f(x);
+        }
+        x
+    }
+
+    /// Move the steps into a `Morphism`.
+    pub fn into_morphism(self) -> Morphism<'static, A>
+        where A: 'static,
+    {
+        self.steps[..self.len].iter().cloned().collect()
+    }
+}
+
+impl<A, const N: usize> Default for ConstMorphism<A, N> {
+    fn default() -> ConstMorphism<A, N> {
+        ConstMorphism::new()
+    }
+}
+
+impl<A, const N: usize> Clone for ConstMorphism<A, N> {
+    fn clone(&self) -> ConstMorphism<A, N> {
+        ConstMorphism {
+            steps: self.steps,
+            len: self.len,
+        }
+    }
+}
+
+impl<A: 'static, const N: usize> From<ConstMorphism<A, N>> for Morphism<'static, A> {
+    fn from(f: ConstMorphism<A, N>) -> Morphism<'static, A> {
+        f.into_morphism()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConstMorphism;
+    use crate::either::Either;
+    use crate::morphism::Morphism;
+
+    fn inc(x: u64) -> u64 {
+        x + 1
+    }
+
+    #[test]
+    fn spill_when_full() {
+        let mut f = Either::Left(ConstMorphism::<u64, 4>::new());
+        for i in 0..6 {
+            f = match f {
+                Either::Left(f) => {
+                    assert_eq!(f.len(), i);
+                    f.tail(inc)
+                }
+                Either::Right(f) => Either::Right(f.tail(inc)),
+            };
+            match f {
+                Either::Left(_) => assert!(i < 4),
+                Either::Right(_) => assert!(i >= 4),
+            }
+        }
+        match f {
+            Either::Right(f) => assert_eq!((f.len(), f.run(0)), (6, 6)),
+            Either::Left(_) => panic!("expected a Morphism"),
+        }
+        let g = match ConstMorphism::<u64, 2>::new().tail(inc) {
+            Either::Left(g) => g,
+            Either::Right(_) => panic!("expected a ConstMorphism"),
+        };
+        assert_eq!(g.run(1), 2);
+        assert_eq!(Morphism::from(g).run(1), 2);
+        assert!(ConstMorphism::<u64, 0>::default().is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+//! Chains of closures that may break out early.
+
+use std::ops::ControlFlow;
+
+use super::{
+    TryMorphism,
+    Void,
+};
+
+/// A suspended chain of closures returning `ControlFlow`, that behaves
+/// as a function from `A` to `ControlFlow<R, B>`.
+///
+/// Running the chain stops at the first closure that returns `Break`,
+/// and the value it breaks with becomes the result.
+pub struct FlowMorphism<'a, A, B, R> {
+    chain: TryMorphism<'a, A, B, R>,
+}
+
+impl FlowMorphism<'static, Void, Void, Void> {
+    /// Create the identity chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::ops::ControlFlow;
+    /// use coyoneda::morphism::FlowMorphism;
+    ///
+    /// let f = FlowMorphism::new::<u64, ()>();
+    /// assert_eq!(f.run(42u64), ControlFlow::Continue(42u64));
+    /// ```
+    #[inline]
+    pub fn new<'a, A, R>() -> FlowMorphism<'a, A, A, R> {
+        FlowMorphism {
+            chain: TryMorphism::new(),
+        }
+    }
+}
+
+impl<'a, B: 'a, C, R> FlowMorphism<'a, B, C, R> {
+    /// Attach a closure to the front of the chain (pre-composition).
+    #[inline]
+    pub fn head<A: 'a, F>(self, f: F) -> FlowMorphism<'a, A, C, R>
+        where F: Fn(A) -> ControlFlow<R, B> + 'a,
+    {
+        FlowMorphism {
+            chain: self.chain.head(move |x| into_result(f(x))),
+        }
+    }
+}
+
+impl<'a, A, B: 'a, R> FlowMorphism<'a, A, B, R> {
+    /// Attach a closure to the back of the chain (post-composition).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::ops::ControlFlow;
+    /// use coyoneda::morphism::FlowMorphism;
+    ///
+    /// let f = FlowMorphism::new::<u64, u64>()
+    ///     .tail(|x| if x > 10 { ControlFlow::Break(x) } else { ControlFlow::Continue(x * 2) })
+    ///     .tail(|x| ControlFlow::Continue(x + 1));
+    /// assert_eq!(f.run(4), ControlFlow::Continue(9));
+    /// assert_eq!(f.run(20), ControlFlow::Break(20));
+    /// ```
+    #[inline]
+    pub fn tail<C: 'a, F>(self, f: F) -> FlowMorphism<'a, A, C, R>
+        where F: Fn(B) -> ControlFlow<R, C> + 'a,
+    {
+        FlowMorphism {
+            chain: self.chain.tail(move |x| into_result(f(x))),
+        }
+    }
+
+    /// Compose one `FlowMorphism` with another.
+    #[inline]
+    pub fn then<C: 'a>(self, other: FlowMorphism<'a, B, C, R>) -> FlowMorphism<'a, A, C, R> {
+        FlowMorphism {
+            chain: self.chain.then(other.chain),
+        }
+    }
+
+    /// Given an argument, run the chain of closures in a loop until one
+    /// of them breaks, and return the final result or the value it broke
+    /// with.
+    #[inline]
+    pub fn run(&self, x: A) -> ControlFlow<R, B> {
+        match self.chain.run(x) {
+            Ok(y) => ControlFlow::Continue(y),
+            Err(r) => ControlFlow::Break(r)
+        }
+    }
+}
+
+impl<'a, A, B: 'a> FlowMorphism<'a, A, B, B> {
+    /// Like `run`, for chains that break with the same type they end
+    /// with, returning the value either way.
+    #[inline]
+    pub fn run_value(&self, x: A) -> B {
+        match self.chain.run(x) {
+            Ok(y) | Err(y) => y
+        }
+    }
+}
+
+#[inline(always)]
+fn into_result<R, C>(x: ControlFlow<R, C>) -> Result<C, R> {
+    match x {
+        ControlFlow::Continue(c) => Ok(c),
+        ControlFlow::Break(r) => Err(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::ops::ControlFlow;
+
+    use super::FlowMorphism;
+
+    #[test]
+    fn break_early() {
+        let calls = Cell::new(0);
+        let mut f = FlowMorphism::new::<Vec<u64>, usize>();
+        for i in 0..10 {
+            f = f.tail(move |v| if v.contains(&i) { ControlFlow::Break(i as usize) } else { ControlFlow::Continue(v) });
+        }
+        let f = f
+            .tail(|v| { calls.set(calls.get() + 1); ControlFlow::Continue(v.len()) })
+            .head(|s: &str| ControlFlow::Continue(s.bytes().map(|b| (b - b'0') as u64).collect()));
+        assert_eq!(f.run("975"), ControlFlow::Break(5));
+        assert_eq!(calls.get(), 0);
+        assert_eq!(f.run_value("9"), 9);
+        assert_eq!(f.run("").continue_value(), Some(0));
+        assert_eq!(calls.get(), 1);
+    }
+}
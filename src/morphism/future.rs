@@ -0,0 +1,164 @@
+//! Chains of asynchronous closures, available with the `futures`
+//! feature.
+
+use std::future::Future;
+
+use futures::{FutureExt, StreamExt};
+use futures::future::{self, LocalBoxFuture};
+use futures::stream;
+
+use super::Void;
+use super::points::{Point, Points};
+
+type Step<'a> = Box<dyn Fn() -> LocalBoxFuture<'a, ()> + 'a>;
+
+/// A suspended chain of asynchronous closures that behave as an async
+/// function from `A` to `B`.
+///
+/// The future returned by `run` awaits each step in turn from a loop,
+/// so like `Morphism` the chain can grow without bound. It borrows the
+/// chain mutably, which keeps two runs from interleaving.
+pub struct AsyncMorphism<'a, A, B = A> {
+    // Each step puts its result into the point after it once its future
+    // is ready.
+    chain: Points<Step<'a>, A, B>,
+}
+
+impl AsyncMorphism<'static, Void> {
+    /// Create the identity chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use futures::executor::block_on;
+    /// use coyoneda::morphism::AsyncMorphism;
+    ///
+    /// let mut f = AsyncMorphism::new::<u64>();
+    /// assert_eq!(block_on(f.run(42u64)), 42u64);
+    /// ```
+    #[inline]
+    pub fn new<'a, A>() -> AsyncMorphism<'a, A> {
+        AsyncMorphism {
+            chain: Points::new(),
+        }
+    }
+}
+
+impl<'a, B: 'a, C> AsyncMorphism<'a, B, C> {
+    /// Attach an asynchronous closure to the front of the chain
+    /// (pre-composition).
+    #[inline]
+    pub fn head<A: 'a, F, R>(self, f: F) -> AsyncMorphism<'a, A, C>
+        where F: Fn(A) -> R + 'a, R: Future<Output=B> + 'a,
+    {
+        AsyncMorphism {
+            chain: self.chain.head(|src, dst| Box::new(move || step(&src, &dst, &f))),
+        }
+    }
+}
+
+impl<'a, A, B: 'a> AsyncMorphism<'a, A, B> {
+    /// Attach an asynchronous closure to the back of the chain
+    /// (post-composition).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use futures::executor::block_on;
+    /// use futures::future;
+    /// use coyoneda::morphism::AsyncMorphism;
+    ///
+    /// let mut f = AsyncMorphism::new::<u64>()
+    ///     .tail(|x| future::ready(x + 1))
+    ///     .tail(|x| future::ready(x.to_string()));
+    /// assert_eq!(block_on(f.run(41u64)), "42");
+    /// ```
+    #[inline]
+    pub fn tail<C: 'a, F, R>(self, f: F) -> AsyncMorphism<'a, A, C>
+        where F: Fn(B) -> R + 'a, R: Future<Output=C> + 'a,
+    {
+        AsyncMorphism {
+            chain: self.chain.tail(|src, dst| Box::new(move || step(&src, &dst, &f))),
+        }
+    }
+
+    /// Compose one `AsyncMorphism` with another.
+    #[inline]
+    pub fn then<C: 'a>(self, other: AsyncMorphism<'a, B, C>) -> AsyncMorphism<'a, A, C> {
+        let join = |src: Point<B>, dst: Point<B>| -> Step<'a> {
+            Box::new(move || {
+                dst.set(src.take());
+                future::ready(()).boxed_local()
+            })
+        };
+        AsyncMorphism {
+            chain: self.chain.then(other.chain, join),
+        }
+    }
+
+    /// Given an argument, return a future that awaits each closure of
+    /// the chain in a loop, and resolves to the final result.
+    ///
+    /// The argument is handed to the chain when the future is first
+    /// polled, so a future that is dropped unpolled drops it as well.
+    #[inline]
+    pub fn run<'s>(&'s mut self, x: A) -> LocalBoxFuture<'s, B> {
+        let chain = &self.chain;
+        future::lazy(move |_| chain.put(x))
+            .then(move |()| stream::iter(chain.steps()).for_each(|f| f()))
+            .map(move |()| chain.take())
+            .boxed_local()
+    }
+}
+
+#[inline(always)]
+fn step<'a, X, Y: 'a, F, R>(src: &Point<X>, dst: &Point<Y>, f: &F) -> LocalBoxFuture<'a, ()>
+    where F: Fn(X) -> R, R: Future<Output=Y> + 'a,
+{
+    let dst = dst.clone();
+    match src.take() {
+        Some(x) => f(x).map(move |y| dst.set(Some(y))).boxed_local(),
+        None => unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use futures::executor::block_on;
+    use futures::future;
+
+    use super::AsyncMorphism;
+
+    #[test]
+    fn await_steps() {
+        let f = AsyncMorphism::new::<u32>()
+            .tail(|x| future::lazy(move |_| x * 2))
+            .head(|s: &str| future::ready(s.len() as u32));
+        let g = AsyncMorphism::new::<u32>().tail(|x| future::ready(x.to_string()));
+        let mut h = f.then(g);
+        assert_eq!(block_on(h.run("abc")), "6");
+        assert_eq!(block_on(h.run("")), "0");
+    }
+
+    #[test]
+    fn drop_unpolled() {
+        let x = Rc::new(1u32);
+        let mut f = AsyncMorphism::new::<Rc<u32>>().tail(|x| future::ready(*x + 1));
+        drop(f.run(x.clone()));
+        assert_eq!(Rc::strong_count(&x), 1);
+        assert_eq!(block_on(f.run(x.clone())), 2);
+        assert_eq!(Rc::strong_count(&x), 1);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn long_chain() {
+        let mut f = AsyncMorphism::new::<u64>();
+        for _ in 0..100000 {
+            f = f.tail(|x| future::ready(x + 1));
+        }
+        assert_eq!(block_on(f.run(0)), 100000);
+    }
+}
@@ -0,0 +1,106 @@
+//! Pairs of chains that undo each other.
+
+use crate::functor::isomorphism;
+
+use super::Morphism;
+
+/// A conversion from `A` to `B` along with its inverse, each a
+/// `Morphism`.
+///
+/// `Iso` does not check that the two chains really are inverse to each
+/// other, but composing and inverting keeps them paired up correctly.
+///
+/// # Example
+///
+/// ```rust
+/// use coyoneda::morphism::{Iso, Morphism};
+///
+/// let hex = Iso::new(
+///     Morphism::new::<u32>().tail(|x| format!("{:x}", x)),
+///     Morphism::new::<String>().tail(|s| u32::from_str_radix(&s, 16).unwrap()),
+/// );
+/// assert_eq!(hex.forward(255), "ff");
+/// assert_eq!(hex.invert().forward("ff".to_string()), 255);
+/// ```
+pub struct Iso<'a, A, B> {
+    forward: Morphism<'a, A, B>,
+    backward: Morphism<'a, B, A>,
+}
+
+impl<'a, A, B> Iso<'a, A, B> {
+    /// Pair up a chain with its inverse.
+    pub fn new(forward: Morphism<'a, A, B>, backward: Morphism<'a, B, A>) -> Iso<'a, A, B> {
+        Iso{forward, backward}
+    }
+
+    /// Swap the directions.
+    pub fn invert(self) -> Iso<'a, B, A> {
+        Iso{forward: self.backward, backward: self.forward}
+    }
+
+    /// Take the pair apart.
+    pub fn into_parts(self) -> (Morphism<'a, A, B>, Morphism<'a, B, A>) {
+        (self.forward, self.backward)
+    }
+}
+
+impl<'a, A: 'a, B: 'a> Iso<'a, A, B> {
+    /// Pair up a closure with its inverse.
+    pub fn from_fns<F, G>(f: F, g: G) -> Iso<'a, A, B>
+        where F: Fn(A) -> B + 'a, G: Fn(B) -> A + 'a,
+    {
+        Iso::new(Morphism::new().tail(f), Morphism::new().tail(g))
+    }
+
+    /// Convert from `A` to `B`.
+    pub fn forward(&self, a: A) -> B {
+        self.forward.run(a)
+    }
+
+    /// Convert from `B` back to `A`.
+    pub fn backward(&self, b: B) -> A {
+        self.backward.run(b)
+    }
+
+    /// Convert from `A` to `B`, and then on to `C` with `other`.
+    pub fn compose<C: 'a>(self, other: Iso<'a, B, C>) -> Iso<'a, A, C> {
+        Iso {
+            forward: self.forward.then(other.forward),
+            backward: other.backward.then(self.backward),
+        }
+    }
+}
+
+impl<'a, A, B> Clone for Iso<'a, A, B> {
+    fn clone(&self) -> Iso<'a, A, B> {
+        Iso{forward: self.forward.clone(), backward: self.backward.clone()}
+    }
+}
+
+impl<'a, A: 'a, B: 'a> isomorphism::Iso<A, B> for Iso<'a, A, B> {
+    fn to(&self, a: A) -> B {
+        self.forward(a)
+    }
+
+    fn from(&self, b: B) -> A {
+        self.backward(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Iso;
+    use crate::functor::isomorphism;
+
+    #[test]
+    fn compose_and_invert() {
+        let double = Iso::from_fns(|x: i64| x * 2, |x| x / 2);
+        let show = Iso::from_fns(|x: i64| x.to_string(), |s: String| s.parse().unwrap());
+        let f = double.clone().compose(show);
+        assert_eq!(f.forward(21), "42");
+        assert_eq!(f.backward("42".to_string()), 21);
+        let g = f.invert();
+        assert_eq!(g.forward("8".to_string()), 4);
+        assert_eq!(isomorphism::Iso::to(&double, 5), 10);
+    }
+}
@@ -0,0 +1,108 @@
+//! Running a chain over the items of an iterator.
+
+use std::iter::FusedIterator;
+
+use super::{Morphism, Scratch};
+
+/// An iterator that runs a chain on each item of another iterator,
+/// created with `Morphism::map_iter`.
+pub struct MapIter<'a, I, A, B> {
+    morph: Morphism<'a, A, B>,
+    scratch: Scratch,
+    iter: I,
+}
+
+impl<'a, I, A, B> Iterator for MapIter<'a, I, A, B>
+    where I: Iterator<Item=A>,
+{
+    type Item = B;
+
+    #[inline]
+    fn next(&mut self) -> Option<B> {
+        let item = self.iter.next()?;
+        Some(self.morph.ends.run_with(&self.morph.chain, &mut self.scratch, item))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, I, A, B> DoubleEndedIterator for MapIter<'a, I, A, B>
+    where I: DoubleEndedIterator<Item=A>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<B> {
+        let item = self.iter.next_back()?;
+        Some(self.morph.ends.run_with(&self.morph.chain, &mut self.scratch, item))
+    }
+}
+
+impl<'a, I, A, B> ExactSizeIterator for MapIter<'a, I, A, B>
+    where I: ExactSizeIterator<Item=A> {}
+
+impl<'a, I, A, B> FusedIterator for MapIter<'a, I, A, B>
+    where I: FusedIterator<Item=A> {}
+
+impl<'a, A, B> Morphism<'a, A, B> {
+    /// Run the chain on each item of `iter`, lazily.
+    ///
+    /// Every item goes through the same steps. With the `unsafe-morphism`
+    /// feature, on by default, values are handed on through a slot that is only
+    /// allocated on the heap when one of them is larger than 64 bytes or
+    /// aligned to more than 16; the iterator allocates that slot on the
+    /// first item and reuses it for the others, so nothing is allocated
+    /// per item either way.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<u64>().tail(|x| x * 2).tail(|x| x.to_string());
+    /// let ys: Vec<String> = f.map_iter(1..4).collect();
+    /// assert_eq!(ys, ["2", "4", "6"]);
+    /// ```
+    #[inline]
+    pub fn map_iter<I>(self, iter: I) -> MapIter<'a, I::IntoIter, A, B>
+        where I: IntoIterator<Item=A>,
+    {
+        MapIter {
+            morph: self,
+            scratch: Scratch::default(),
+            iter: iter.into_iter(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::morphism::Morphism;
+
+    #[test]
+    fn map_iter() {
+        let f = Morphism::new::<u64>().tail(|x| x + 1).head(|s: &str| s.len() as u64);
+        let mut it = f.map_iter(vec!["a", "bb", "ccc", "dddd"]);
+        assert_eq!(it.len(), 4);
+        assert_eq!(it.next(), Some(2));
+        assert_eq!(it.next_back(), Some(5));
+        assert_eq!(it.collect::<Vec<_>>(), [3, 4]);
+    }
+
+    #[test]
+    fn map_iter_large_values() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[repr(align(32))]
+        struct Big([u64; 16]);
+
+        let f = Morphism::new::<u64>()
+            .tail(|x| Big([x; 16]))
+            .tail(|mut b: Big| { b.0[15] += 1; b })
+            .tail(|b| b.0.iter().sum::<u64>());
+        let mut it = f.map_iter(1..5);
+        assert_eq!(it.next(), Some(17));
+        assert_eq!(it.next_back(), Some(65));
+        assert_eq!(it.collect::<Vec<_>>(), [33, 49]);
+    }
+}
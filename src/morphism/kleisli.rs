@@ -0,0 +1,153 @@
+//! Chains of monadic closures.
+
+use std::rc::Rc;
+
+use crate::functor::{Monad, Pure};
+use crate::functor::parametric::ReParam;
+
+use super::Morphism;
+
+/// A suspended chain of closures `Fn(X) -> M<Y>` that behave as a
+/// function from `A` to `M<B>`, for a monad `M`.
+///
+/// `M` stands for the monad itself and can be instantiated at any
+/// parameter, such as `Option<()>` or `Result<(), E>`. Each closure is
+/// bound in turn from a loop, so the chain can grow without bound.
+pub struct Kleisli<'a, M, A, B = A>
+    where M: ReParam<A> + ReParam<B>,
+{
+    // The steps are lifted to `M<X> -> M<Y>` by binding them, so the
+    // chain starts at `M<A>`.
+    morph: Morphism<'a, <M as ReParam<A>>::Output, <M as ReParam<B>>::Output>,
+}
+
+impl<'a, M, A> Kleisli<'a, M, A>
+    where M: ReParam<A>,
+{
+    /// Create the identity chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Kleisli;
+    ///
+    /// let f = Kleisli::<Option<()>, u64>::new();
+    /// assert_eq!(f.run(42u64), Some(42u64));
+    /// ```
+    #[inline]
+    pub fn new() -> Kleisli<'a, M, A> {
+        Kleisli {
+            morph: Morphism::new(),
+        }
+    }
+}
+
+impl<'a, M, A> Default for Kleisli<'a, M, A>
+    where M: ReParam<A>,
+{
+    fn default() -> Kleisli<'a, M, A> {
+        Kleisli::new()
+    }
+}
+
+impl<'a, M, B, C> Kleisli<'a, M, B, C>
+    where M: ReParam<B> + ReParam<C>, <M as ReParam<B>>::Output: 'a,
+{
+    /// Attach a monadic closure to the front of the chain
+    /// (pre-composition).
+    #[inline]
+    pub fn head<A, F>(self, f: F) -> Kleisli<'a, M, A, C>
+        where M: ReParam<A>,
+              <M as ReParam<A>>::Output: Monad<'a, B, Output=<M as ReParam<B>>::Output> + 'a,
+              F: Fn(A) -> <M as ReParam<B>>::Output + 'a,
+    {
+        let f = Rc::new(f);
+        Kleisli {
+            morph: self.morph.head(move |m: <M as ReParam<A>>::Output| {
+                let f = f.clone();
+                m.bind(move |x| f(x))
+            }),
+        }
+    }
+}
+
+impl<'a, M, A, B> Kleisli<'a, M, A, B>
+    where M: ReParam<A> + ReParam<B>, <M as ReParam<B>>::Output: 'a,
+{
+    /// Attach a monadic closure to the back of the chain
+    /// (post-composition).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Kleisli;
+    ///
+    /// let f = Kleisli::<Option<()>, &str>::new()
+    ///     .tail(|s| s.parse::<u8>().ok())
+    ///     .tail(|x| x.checked_mul(2));
+    /// assert_eq!(f.run("21"), Some(42));
+    /// assert_eq!(f.run("200"), None);
+    /// ```
+    #[inline]
+    pub fn tail<C, F>(self, f: F) -> Kleisli<'a, M, A, C>
+        where M: ReParam<C>,
+              <M as ReParam<B>>::Output: Monad<'a, C, Output=<M as ReParam<C>>::Output>,
+              <M as ReParam<C>>::Output: 'a,
+              F: Fn(B) -> <M as ReParam<C>>::Output + 'a,
+    {
+        let f = Rc::new(f);
+        Kleisli {
+            morph: self.morph.tail(move |m: <M as ReParam<B>>::Output| {
+                let f = f.clone();
+                m.bind(move |x| f(x))
+            }),
+        }
+    }
+
+    /// Compose one `Kleisli` chain with another.
+    #[inline]
+    pub fn then<C>(self, other: Kleisli<'a, M, B, C>) -> Kleisli<'a, M, A, C>
+        where M: ReParam<C>, <M as ReParam<C>>::Output: 'a,
+    {
+        Kleisli {
+            morph: self.morph.then(other.morph),
+        }
+    }
+
+    /// Given an argument, bind each closure of the chain in a loop and
+    /// return the final result.
+    #[inline]
+    pub fn run(&self, x: A) -> <M as ReParam<B>>::Output
+        where <M as ReParam<A>>::Output: Pure,
+    {
+        self.morph.run(Pure::pure(x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Kleisli;
+    use crate::thunk::Thunk;
+
+    #[test]
+    fn bind_steps() {
+        let f = Kleisli::<Result<(), String>, u32>::new()
+            .tail(|x| x.checked_sub(1).ok_or("underflow".to_string()))
+            .head(|s: &str| s.parse().map_err(|_| format!("not a number: {}", s)));
+        let g = Kleisli::<Result<(), String>, u32>::new().tail(|x| Ok(x * 2));
+        let h = f.then(g);
+        assert_eq!(h.run("22"), Ok(42));
+        assert_eq!(h.run("0"), Err("underflow".to_string()));
+        assert_eq!(h.run("x"), Err("not a number: x".to_string()));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn long_chain() {
+        let mut f = Kleisli::<Thunk<()>, u64>::new();
+        for _ in 0..100000 {
+            f = f.tail(|x| Thunk::new(move || x + 1));
+        }
+        assert_eq!(f.run(0).into_inner(), 100000);
+    }
+}
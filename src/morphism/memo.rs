@@ -0,0 +1,98 @@
+//! Caching the results of a chain.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::Morphism;
+
+/// A `Morphism` that remembers its results, created with
+/// `Morphism::memoized`.
+///
+/// Running it with an argument it has seen before returns a clone of the
+/// cached result instead of running the chain again.
+pub struct Memoized<'a, A, B> {
+    morph: Morphism<'a, A, B>,
+    cache: RefCell<HashMap<A, B>>,
+}
+
+impl<'a, A, B> Memoized<'a, A, B>
+    where A: Hash + Eq + Clone, B: Clone + 'a,
+{
+    /// Look up the result for `x`, running the chain on a miss.
+    #[inline]
+    pub fn run(&self, x: A) -> B {
+        if let Some(y) = self.cache.borrow().get(&x) {
+            return y.clone();
+        }
+        // The cache is not borrowed while the chain runs, so that its
+        // closures may run this again.
+        let y = self.morph.run(x.clone());
+        self.cache.borrow_mut().insert(x, y.clone());
+        y
+    }
+}
+
+impl<'a, A, B> Memoized<'a, A, B> {
+    /// The number of cached results.
+    pub fn cached(&self) -> usize {
+        self.cache.borrow().len()
+    }
+
+    /// Forget all cached results.
+    pub fn clear(&self) {
+        self.cache.borrow_mut().clear()
+    }
+
+    /// Give up the cache, and return the chain.
+    pub fn into_inner(self) -> Morphism<'a, A, B> {
+        self.morph
+    }
+}
+
+impl<'a, A, B> Morphism<'a, A, B>
+    where A: Hash + Eq + Clone, B: Clone + 'a,
+{
+    /// Wrap the chain in a cache keyed by its argument.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<u64>().tail(|x| x * x).memoized();
+    /// assert_eq!(f.run(12), 144);
+    /// assert_eq!(f.run(12), 144);
+    /// assert_eq!(f.cached(), 1);
+    /// ```
+    pub fn memoized(self) -> Memoized<'a, A, B> {
+        Memoized {
+            morph: self,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use crate::morphism::Morphism;
+
+    #[test]
+    fn run_once_per_argument() {
+        let calls = Cell::new(0);
+        let f = Morphism::new::<String>()
+            .tail(|s| { calls.set(calls.get() + 1); s.len() })
+            .memoized();
+        for s in &["a", "bc", "a", "a", "bc"] {
+            assert_eq!(f.run(s.to_string()), s.len());
+        }
+        assert_eq!(calls.get(), 2);
+        assert_eq!(f.cached(), 2);
+        f.clear();
+        assert_eq!(f.run("a".to_string()), 1);
+        assert_eq!(calls.get(), 3);
+        assert_eq!(f.into_inner().run("abc".to_string()), 3);
+    }
+}
@@ -0,0 +1,1855 @@
+//! This module provides a structure for suspended closure composition.
+//! Composition is delayed and executed in a loop when a `Morphism` is
+//! applied to an argument.
+//!
+//! The motivation for `Morphism` is to provide a means of composing
+//! and evaluating an unbounded (within heap constraints) number of
+//! closures without blowing the stack. In other words, `Morphism` is
+//! one way to work around the lack of tail-call optimization in Rust.
+//!
+//! `Morphism` underlies `Coyoneda`, and is exported so that downstream
+//! crates can build their own suspensions on top of it. Its safe API is
+//! stable and follows the crate's semver guarantees; the way steps are
+//! stored is an implementation detail and may change between releases.
+//!
+//! With the `bumpalo` feature, a chain can be created with
+//! `Morphism::new_in` so that its closures are allocated from a bump
+//! arena rather than allocated one by one.
+//!
+//! With the `allocator-api` feature, which needs a nightly compiler,
+//! `Morphism::new_in_allocator` does the same for any allocator
+//! implementing `std::alloc::Allocator`.
+//!
+//! With the `nightly` feature, which needs a nightly compiler,
+//! `Morphism` implements the `Fn` traits, so that it can be passed
+//! wherever a closure is expected.
+//!
+//! With the `unsafe-morphism` feature, which is on by default, values
+//! are moved from one step to the next through a raw scratch slot.
+//! Without it, they are handed on through shared cells, without any
+//! unsafe code. Both behave the same, but the cells are captured by the
+//! closures of the chain, so every type along it has to outlive the
+//! chain: see `Outlives`.
+//!
+//! Vendored from the `morphism` crate by Darin Morrison (MIT).
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::fmt;
+use std::iter::FromIterator;
+use std::mem;
+use std::ops::{
+    Deref,
+    Shl,
+    Shr,
+};
+use std::panic::{
+    self,
+    AssertUnwindSafe,
+};
+use std::rc::Rc;
+use std::vec;
+
+#[cfg(feature = "allocator-api")]
+use std::alloc::Allocator;
+
+#[cfg(feature = "bumpalo")]
+use bumpalo::Bump;
+
+use crate::either::Either;
+
+mod endo;
+mod fallible;
+mod fixed;
+mod flow;
+#[cfg(feature = "futures")]
+mod future;
+mod iso;
+mod iter;
+mod kleisli;
+mod memo;
+mod mutable;
+mod once;
+mod optional;
+mod persistent;
+mod points;
+mod sync;
+
+pub use self::endo::Endo;
+pub use self::fallible::TryMorphism;
+pub use self::fixed::ConstMorphism;
+pub use self::flow::FlowMorphism;
+#[cfg(feature = "futures")]
+pub use self::future::AsyncMorphism;
+pub use self::iso::Iso;
+pub use self::iter::MapIter;
+pub use self::kleisli::Kleisli;
+pub use self::memo::Memoized;
+pub use self::mutable::MutMorphism;
+pub use self::once::OnceMorphism;
+pub use self::optional::OptionMorphism;
+pub use self::persistent::PersistentMorphism;
+pub use self::sync::SyncMorphism;
+
+#[cfg(not(feature = "unsafe-morphism"))]
+mod cell;
+#[cfg(not(feature = "unsafe-morphism"))]
+use self::cell::{Ends, Scratch, Slot};
+#[cfg(feature = "unsafe-morphism")]
+mod slot;
+#[cfg(feature = "unsafe-morphism")]
+use self::slot::{Ends, Scratch, Slot};
+
+/// A type-erased closure of the chain. How it receives its argument
+/// and hands on its result through the `Slot` depends on the backend.
+type Erased<'a> = dyn Fn(Slot) + 'a;
+
+/// A single step of the chain, along with the label it was attached
+/// with.
+#[derive(Clone)]
+struct Step<'a> {
+    f: Closure<'a>,
+    label: Label,
+}
+
+/// The closure of a step, shared with any clones of the chain,
+/// borrowed from an arena or allocated from a user-supplied allocator.
+#[derive(Clone)]
+enum Closure<'a> {
+    Shared(Rc<Erased<'a>>),
+    #[cfg(feature = "bumpalo")]
+    Arena(&'a Erased<'a>),
+    #[cfg(feature = "allocator-api")]
+    Pooled(Rc<Erased<'a>, &'a dyn Allocator>),
+}
+
+#[derive(Clone, Copy)]
+enum Label {
+    Anonymous,
+    Named(&'static str),
+    // Anonymous closures fused into a single step, at least two.
+    Fused(usize),
+    // Added by the backend to join two chains.
+    #[cfg_attr(feature = "unsafe-morphism", allow(dead_code))]
+    Join,
+}
+
+impl<'a> Deref for Step<'a> {
+    type Target = Erased<'a>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Erased<'a> {
+        match self.f {
+            Closure::Shared(ref f) => &**f,
+            #[cfg(feature = "bumpalo")]
+            Closure::Arena(f) => f,
+            #[cfg(feature = "allocator-api")]
+            Closure::Pooled(ref f) => &**f,
+        }
+    }
+}
+
+/// A suspended chain of closures that behave as a function from type
+/// `A` to type `B`.
+///
+/// When `B = A` the parameter `B` can be omitted: `Morphism<'a, A>`
+/// is equivalent to `Morphism<'a, A, A>`.  This is convenient for
+/// providing annotations with `Morphism::new()`.
+///
+/// Cloning a `Morphism` is cheap: the clone shares its closures with the
+/// original, and both can then be run or extended independently.
+pub struct Morphism<'a, A, B = A> {
+    chain: Box<Chain<'a>>,
+    ends: Ends<A, B>,
+}
+
+impl<'a, A, B> Clone for Morphism<'a, A, B> {
+    fn clone(&self) -> Morphism<'a, A, B> {
+        Morphism {
+            chain: self.chain.clone(),
+            ends: self.ends.clone(),
+        }
+    }
+}
+
+/// Prints the labels of the closures in the order they run, with `_`
+/// for those attached without one.
+impl<'a, A, B> fmt::Debug for Morphism<'a, A, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Anonymous;
+
+        impl fmt::Debug for Anonymous {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("_")
+            }
+        }
+
+        f.write_str("Morphism")?;
+        let mut list = f.debug_list();
+        for step in self.chain.steps() {
+            match step.label {
+                Label::Anonymous => list.entry(&Anonymous),
+                Label::Fused(n) => list.entries((0..n).map(|_| Anonymous)),
+                Label::Named(name) => list.entry(&name),
+                Label::Join => &mut list,
+            };
+        }
+        list.finish()
+    }
+}
+
+// The steps of a chain are `front` in reverse, followed by `middle`,
+// followed by `back`. Keeping two flat vectors at the ends makes pushing
+// at either end amortized O(1), and running the chain a linear walk over
+// contiguous memory. Composing with a longer chain would have to move
+// most of the steps from one vector to another, so the vectors of the
+// longer chain are spliced in as `Segment`s instead, in time that does
+// not depend on the number of steps. They are kept behind a box so that
+// moving a `Morphism` around while building it stays cheap.
+//
+// Each closure is allocated on its own, so that clones of the chain can
+// share it, and the safe backend allocates a cell for every point
+// between two steps on top of that.
+//
+// Adjacent steps are only fused into one closure below the threshold
+// set with `fuse_below`, which is zero unless changed. Once a closure is
+// erased, fusing can only nest the earlier step inside the later one,
+// which still makes one dynamic call per step and adds a level of
+// indirection on top: the `run_fused` cases in `benches/morphism.rs`
+// are slower than the flat loop at every length, on both backends.
+// Fused steps are labelled with the number of closures in them, so
+// that `len` and `Debug` count them one by one, and named steps are
+// never fused.
+//
+// The types at either end of the chain, and whatever the backend needs
+// to feed values in and out, are kept alongside it in `Ends`. Steps the
+// backend adds to join two chains are counted in `joins`, so that they
+// can be told apart from the closures attached by the user.
+//
+// Once steps are erased, the type passed from one to the next is gone,
+// so a chain can only be cut where that type is known. This is the case
+// while every step maps a value in place, as attached with `push_back`
+// or `push_front` to the identity: each one then takes and returns the
+// type of the chain, through the single point the safe backend made
+// for it, or the slot of the unsafe one. `in_place` records this, and
+// is cleared by anything that may attach a step between other types.
+#[derive(Clone)]
+struct Chain<'a> {
+    front: Vec<Step<'a>>,
+    middle: VecDeque<Segment<'a>>,
+    back: Vec<Step<'a>>,
+    joins: usize,
+    fused: usize,
+    fuse_below: usize,
+    in_place: bool,
+    #[cfg(feature = "bumpalo")]
+    arena: Option<&'a Bump>,
+    #[cfg(feature = "allocator-api")]
+    alloc: Option<&'a dyn Allocator>,
+}
+
+impl<'a> Chain<'a> {
+    fn new() -> Chain<'a> {
+        Chain {
+            front: Vec::new(),
+            middle: VecDeque::new(),
+            back: Vec::new(),
+            joins: 0,
+            fused: 0,
+            fuse_below: 0,
+            in_place: true,
+            #[cfg(feature = "bumpalo")]
+            arena: None,
+            #[cfg(feature = "allocator-api")]
+            alloc: None,
+        }
+    }
+
+    fn len(&self) -> usize {
+        let middle: usize = self.middle.iter().map(|segment| segment.steps.len()).sum();
+        self.front.len() + middle + self.back.len()
+    }
+
+    // The number of closures attached by the user.
+    fn closures(&self) -> usize {
+        self.len() - self.joins + self.fused
+    }
+
+    // All steps, in the order they run.
+    #[inline(always)]
+    fn steps<'s>(&'s self) -> impl Iterator<Item=&'s Step<'a>> {
+        self.front.iter().rev()
+            .chain(self.middle.iter().flat_map(Segment::iter))
+            .chain(self.back.iter())
+    }
+
+    // Run the steps of `other` after those of this chain.
+    fn splice_back(&mut self, other: Chain<'a>) {
+        let Chain { front, middle, back, joins, fused, .. } = other;
+        let prefix = mem::replace(&mut self.back, back);
+        self.push_segment(Segment { steps: prefix, reversed: false });
+        self.push_segment(Segment { steps: front, reversed: true });
+        for segment in middle {
+            self.push_segment(segment);
+        }
+        self.joins += joins;
+        self.fused += fused;
+    }
+
+    // Run the steps of `other` before those of this chain.
+    fn splice_front(&mut self, other: Chain<'a>) {
+        let Chain { front, middle, back, joins, fused, .. } = other;
+        let suffix = mem::replace(&mut self.front, front);
+        self.push_segment_front(Segment { steps: suffix, reversed: true });
+        self.push_segment_front(Segment { steps: back, reversed: false });
+        for segment in middle.into_iter().rev() {
+            self.push_segment_front(segment);
+        }
+        self.joins += joins;
+        self.fused += fused;
+    }
+
+    fn push_segment(&mut self, segment: Segment<'a>) {
+        if !segment.steps.is_empty() {
+            self.middle.push_back(segment);
+        }
+    }
+
+    fn push_segment_front(&mut self, segment: Segment<'a>) {
+        if !segment.steps.is_empty() {
+            self.middle.push_front(segment);
+        }
+    }
+
+    #[inline(always)]
+    fn step<F>(&self, f: F) -> Step<'a>
+        where F: Fn(Slot) + 'a,
+    {
+        #[cfg(feature = "bumpalo")]
+        {
+            if let Some(arena) = self.arena {
+                return Step { f: Closure::Arena(arena.alloc(f)), label: Label::Anonymous };
+            }
+        }
+        #[cfg(feature = "allocator-api")]
+        {
+            if let Some(alloc) = self.alloc {
+                return Step { f: Closure::Pooled(Rc::new_in(f, alloc)), label: Label::Anonymous };
+            }
+        }
+        Step { f: Closure::Shared(Rc::new(f)), label: Label::Anonymous }
+    }
+
+    // Move the steps from `at` onwards, in the order they run, into a
+    // new chain with the same arena or allocator. Only sound to run
+    // either part on its own when the chain is `in_place`.
+    fn split_off(&mut self, at: usize) -> Chain<'a> {
+        let (middle, back) = (mem::take(&mut self.middle), mem::take(&mut self.back));
+        let mut steps = self.front.drain(..).rev()
+            .chain(middle.into_iter().flat_map(Segment::into_steps))
+            .chain(back);
+        self.back.extend(steps.by_ref().take(at));
+        let mut rest = Chain::new();
+        rest.back.extend(steps);
+        #[cfg(feature = "bumpalo")]
+        {
+            rest.arena = self.arena;
+        }
+        #[cfg(feature = "allocator-api")]
+        {
+            rest.alloc = self.alloc;
+        }
+        rest
+    }
+
+    // The number of closures in `step`, if the closure about to be
+    // attached next to it should be fused into it.
+    fn fuse_into(&self, step: Option<&Step<'a>>, label: Label) -> Option<usize> {
+        if self.closures() >= self.fuse_below {
+            return None;
+        }
+        match (label, step?.label) {
+            (Label::Anonymous, Label::Anonymous) => Some(1),
+            (Label::Anonymous, Label::Fused(n)) => Some(n),
+            _ => None,
+        }
+    }
+
+    // Push a step that runs before all others, or fuse it into the first
+    // one.
+    #[inline(always)]
+    fn push_head<F>(&mut self, label: Label, f: F)
+        where F: Fn(Slot) + 'a,
+    {
+        match self.fuse_into(self.front.last(), label) {
+            Some(n) => {
+                let next = self.front.pop().unwrap();
+                let step = self.step(move |slot| { f(slot); next(slot) });
+                self.front.push(Step { label: Label::Fused(n + 1), ..step });
+                self.fused += 1;
+            }
+            None => {
+                let step = self.step(f);
+                self.front.push(Step { label, ..step });
+            }
+        }
+    }
+
+    // Push a step that runs after all others, or fuse it into the last
+    // one.
+    #[inline(always)]
+    fn push_tail<F>(&mut self, label: Label, f: F)
+        where F: Fn(Slot) + 'a,
+    {
+        match self.fuse_into(self.back.last(), label) {
+            Some(n) => {
+                let prev = self.back.pop().unwrap();
+                let step = self.step(move |slot| { prev(slot); f(slot) });
+                self.back.push(Step { label: Label::Fused(n + 1), ..step });
+                self.fused += 1;
+            }
+            None => {
+                let step = self.step(f);
+                self.back.push(Step { label, ..step });
+            }
+        }
+    }
+
+    // Push a step that joins the back of this chain to the front of
+    // another one.
+    #[cfg_attr(feature = "unsafe-morphism", allow(dead_code))]
+    #[inline(always)]
+    fn push_join<F>(&mut self, f: F)
+        where F: Fn(Slot) + 'a,
+    {
+        let step = self.step(f);
+        self.back.push(Step { label: Label::Join, ..step });
+        self.joins += 1;
+    }
+}
+
+// A vector of steps spliced into the middle of a chain, kept in the
+// order they run unless `reversed`.
+#[derive(Clone)]
+struct Segment<'a> {
+    steps: Vec<Step<'a>>,
+    reversed: bool,
+}
+
+impl<'a> Segment<'a> {
+    #[inline(always)]
+    fn iter<'s>(&'s self) -> impl Iterator<Item=&'s Step<'a>> {
+        let (forward, reversed) = if self.reversed {
+            (&[][..], &self.steps[..])
+        } else {
+            (&self.steps[..], &[][..])
+        };
+        forward.iter().chain(reversed.iter().rev())
+    }
+
+    fn into_steps(mut self) -> vec::IntoIter<Step<'a>> {
+        if self.reversed {
+            self.steps.reverse();
+        }
+        self.steps.into_iter()
+    }
+}
+
+/// Build a `Morphism` from closures that are known when it is written,
+/// composing them at compile time.
+///
+/// `morphism!(f, g, h)` runs `f`, then `g`, then `h`, like
+/// `Morphism::new().tail(f).tail(g).tail(h)`. The closures are fused into
+/// a single step, so running it makes one dynamic call and no others,
+/// while the result can still be extended and composed with chains built
+/// at run time.
+///
+/// # Example
+///
+/// ```rust
+/// use coyoneda::morphism;
+///
+/// let f = morphism!(|x: u64| x + 1, |x| x * 2, |x: u64| x.to_string());
+/// assert_eq!(f.len(), 1);
+/// assert_eq!(f.run(20), "42");
+/// ```
+#[macro_export]
+macro_rules! morphism {
+    () => {
+        $crate::morphism::Morphism::new()
+    };
+    ($f:expr $(, $g:expr)* $(,)*) => {
+        $crate::morphism::Morphism::new().tail($crate::morphism!(@fuse $f; $($g),*))
+    };
+    (@fuse $f:expr;) => {
+        $f
+    };
+    (@fuse $f:expr; $g:expr $(, $h:expr)*) => {{
+        let (f, g) = ($f, $g);
+        $crate::morphism!(@fuse move |x| g(f(x)); $($h),*)
+    }};
+}
+
+/// The types that can be passed between the closures of a
+/// `Morphism<'a, ..>`.
+///
+/// With the `unsafe-morphism` feature, this is every type. Without it,
+/// only types that outlive `'a` are, as the closures capture the cells
+/// that values are handed on through.
+pub trait Outlives<'a>: OutlivesBound<'a> {}
+
+impl<'a, T: ?Sized + OutlivesBound<'a>> Outlives<'a> for T {}
+
+#[cfg(feature = "unsafe-morphism")]
+#[doc(hidden)]
+pub trait OutlivesBound<'a> {}
+#[cfg(feature = "unsafe-morphism")]
+impl<'a, T: ?Sized> OutlivesBound<'a> for T {}
+
+#[cfg(not(feature = "unsafe-morphism"))]
+#[doc(hidden)]
+pub trait OutlivesBound<'a>: 'a {}
+#[cfg(not(feature = "unsafe-morphism"))]
+impl<'a, T: ?Sized + 'a> OutlivesBound<'a> for T {}
+
+/// An uninhabited placeholder, so that `Morphism::new` can be called
+/// without naming the type it is implemented on.
+pub enum Void {}
+impl Morphism<'static, Void> {
+    /// Create the identity chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// assert_eq!(Morphism::new::<u64>().run(42u64), 42u64);
+    /// ```
+    #[inline]
+    pub fn new<'a, A>() -> Morphism<'a, A> {
+        Morphism {
+            chain: Box::new(Chain::new()),
+            ends: Ends::identity(),
+        }
+    }
+
+    /// Create the identity chain, allocating the closures that are
+    /// later attached to it from `arena`.
+    ///
+    /// The arena never runs destructors, so anything captured by those
+    /// closures is leaked rather than dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bumpalo::Bump;
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let arena = Bump::new();
+    /// let f = Morphism::new_in::<u64>(&arena).tail(|x| x + 1);
+    /// assert_eq!(f.run(41u64), 42u64);
+    /// ```
+    #[cfg(feature = "bumpalo")]
+    #[inline]
+    pub fn new_in<A>(arena: &Bump) -> Morphism<'_, A> {
+        let mut chain = Chain::new();
+        chain.arena = Some(arena);
+        Morphism {
+            chain: Box::new(chain),
+            ends: Ends::identity(),
+        }
+    }
+
+    /// Create the identity chain, allocating the closures that are
+    /// later attached to it from `alloc`.
+    ///
+    /// Unlike with an arena, the closures are dropped and their memory
+    /// handed back to `alloc` along with the chain. The list of steps
+    /// itself is still kept on the global heap.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #![feature(allocator_api)]
+    /// use std::alloc::Global;
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new_in_allocator::<u64>(&Global).tail(|x| x + 1);
+    /// assert_eq!(f.run(41u64), 42u64);
+    /// ```
+    #[cfg(feature = "allocator-api")]
+    #[inline]
+    pub fn new_in_allocator<A>(alloc: &dyn Allocator) -> Morphism<'_, A> {
+        let mut chain = Chain::new();
+        chain.alloc = Some(alloc);
+        Morphism {
+            chain: Box::new(chain),
+            ends: Ends::identity(),
+        }
+    }
+}
+
+/// The identity chain, same as `Morphism::new()`.
+impl<'a, A> Default for Morphism<'a, A> {
+    #[inline]
+    fn default() -> Morphism<'a, A> {
+        Morphism::new()
+    }
+}
+
+impl<'a, B: Outlives<'a>, C> Morphism<'a, B, C> {
+    /// Attach a closure to the front of the closure chain. This corresponds to
+    /// closure composition at the domain (pre-composition).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<Option<String>>()
+    ///     .head(|x: Option<u64>| x.map(|y| y.to_string()))
+    ///     .head(|x: Option<u64>| x.map(|y| y - 42u64))
+    ///     .head(|x: u64| Some(x + 42u64 + 42u64));
+    /// assert_eq!(f.run(0u64), Some("42".to_string()));
+    /// ```
+    #[inline]
+    pub fn head<A: Outlives<'a>, F>(self, f: F) -> Morphism<'a, A, C>
+        where F: Fn(A) -> B + 'a,
+    {
+        self.head_labeled(Label::Anonymous, f)
+    }
+
+    /// Like `head`, but labels the closure with `name` when the chain is
+    /// printed with `Debug`.
+    #[inline]
+    pub fn head_named<A: Outlives<'a>, F>(self, name: &'static str, f: F) -> Morphism<'a, A, C>
+        where F: Fn(A) -> B + 'a,
+    {
+        self.head_labeled(Label::Named(name), f)
+    }
+
+    /// Like `head`, but the closure borrows its argument, so the chain
+    /// runs on `&A` without ever cloning or owning the `A`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let words = vec!["a".to_string(), "bc".to_string()];
+    /// let f = Morphism::new::<usize>().tail(|n| n * 2).head_ref(String::len);
+    /// for w in &words {
+    ///     assert_eq!(f.run(w), w.len() * 2);
+    /// }
+    /// ```
+    #[inline]
+    pub fn head_ref<'r, A: ?Sized, F>(self, f: F) -> Morphism<'a, &'r A, C>
+        where F: Fn(&A) -> B + 'a, &'r A: Outlives<'a>,
+    {
+        self.head_labeled(Label::Anonymous, move |x: &'r A| f(x))
+    }
+
+    #[inline(always)]
+    fn head_labeled<A: Outlives<'a>, F>(self, label: Label, f: F) -> Morphism<'a, A, C>
+        where F: Fn(A) -> B + 'a,
+    {
+        let mut chain = self.chain;
+        chain.in_place = false;
+        let ends = self.ends.head(&mut chain, label, f);
+        Morphism {
+            chain,
+            ends,
+        }
+    }
+
+    /// Mutate a given `Morphism<B, C>` by pushing a closure of type
+    /// `Fn(B) -> B` onto the front of the chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let mut f = Morphism::new::<u64>();
+    /// for i in (0..10u64) {
+    ///     (&mut f).push_front(move |x| x + i);
+    /// }
+    /// assert_eq!(f.run(0u64), 45u64);
+    /// ```
+    #[inline]
+    pub fn push_front<F>(&mut self, f: F)
+        where F: Fn(B) -> B + 'a,
+    {
+        self.ends.push_front(&mut self.chain, f)
+    }
+}
+
+impl<'a, A, B: Outlives<'a>> Morphism<'a, A, B> {
+    /// Attach a closure to the back of the closure chain. This corresponds to
+    /// closure composition at the codomain (post-composition).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<u64>()
+    ///     .tail(|x| Some(x + 42u64 + 42u64))
+    ///     .tail(|x| x.map(|y| y - 42u64))
+    ///     .tail(|x| x.map(|y| y.to_string()));
+    /// assert_eq!(f.run(0u64), Some("42".to_string()));
+    /// ```
+    #[inline]
+    pub fn tail<C: Outlives<'a>, F>(self, f: F) -> Morphism<'a, A, C>
+        where F: Fn(B) -> C + 'a,
+    {
+        self.tail_labeled(Label::Anonymous, f)
+    }
+
+    /// Like `tail`, but labels the closure with `name` when the chain is
+    /// printed with `Debug`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<&str>()
+    ///     .tail_named("trim", |s| s.trim())
+    ///     .tail(|s| s.len())
+    ///     .head_named("input", |s: &'static str| s);
+    /// assert_eq!(format!("{:?}", f), r#"Morphism["input", "trim", _]"#);
+    /// ```
+    #[inline]
+    pub fn tail_named<C: Outlives<'a>, F>(self, name: &'static str, f: F) -> Morphism<'a, A, C>
+        where F: Fn(B) -> C + 'a,
+    {
+        self.tail_labeled(Label::Named(name), f)
+    }
+
+    #[inline(always)]
+    fn tail_labeled<C: Outlives<'a>, F>(self, label: Label, f: F) -> Morphism<'a, A, C>
+        where F: Fn(B) -> C + 'a,
+    {
+        let mut chain = self.chain;
+        chain.in_place = false;
+        let ends = self.ends.tail(&mut chain, label, f);
+        Morphism {
+            chain,
+            ends,
+        }
+    }
+
+    /// Mutate a given `Morphism<A, B>` by pushing a closure of type
+    /// `Fn(B) -> B` onto the back of the chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let mut f = Morphism::new::<u64>();
+    /// for i in (0..10u64) {
+    ///     (&mut f).push_back(move |x| x + i);
+    /// }
+    /// assert_eq!(f.run(0u64), 45u64);
+    /// ```
+    #[inline]
+    pub fn push_back<F>(&mut self, f: F)
+        where F: Fn(B) -> B + 'a,
+    {
+        self.ends.push_back(&mut self.chain, f)
+    }
+
+    /// Compose one `Morphism` with another, running `other` after
+    /// `self`. To attach a chain at the front instead, use `then_front`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let mut f = Morphism::new::<u64>();
+    /// for _ in (0..100000u64) {
+    ///     f = f.tail(|x| x + 42u64);
+    /// }
+    /// // the type changes to Morphism<u64, Option<u64>> so rebind f
+    /// let f = f.tail(|x| Some(x));
+    ///
+    /// let mut g = Morphism::new::<Option<u64>>();
+    /// for _ in (0..99999u64) {
+    ///     g = g.tail(|x| x.map(|y| y - 42u64));
+    /// }
+    /// // the type changes to Morphism<Option<u64>, String> so rebind g
+    /// let g = g.tail(|x| x.map(|y| y + 1000u64).unwrap().to_string());
+    ///
+    /// assert_eq!(f.then(g).run(0u64), "1042".to_string());
+    /// ```
+    #[inline]
+    pub fn then<C: Outlives<'a>>(self, other: Morphism<'a, B, C>) -> Morphism<'a, A, C> {
+        // Move the steps of a shorter chain to the back of this one, and
+        // splice in those of a longer one, but keep the storage of `self`
+        // either way, along with its arena.
+        let mut chain = self.chain;
+        chain.in_place = false;
+        let ends = self.ends.then(&mut chain, other.ends);
+        let other = *other.chain;
+        if other.middle.is_empty() && chain.len() >= other.len() {
+            let Chain { front, back, joins, fused, .. } = other;
+            chain.back.extend(front.into_iter().rev());
+            chain.back.extend(back);
+            chain.joins += joins;
+            chain.fused += fused;
+        } else {
+            chain.splice_back(other);
+        }
+        Morphism {
+            chain,
+            ends,
+        }
+    }
+
+    /// Attach a whole `Morphism` to the front of the chain, running
+    /// `other` first. This corresponds to `head`, for a chain of closures
+    /// rather than a single one.
+    ///
+    /// The steps of `other` are spliced in without being moved one by
+    /// one, so this takes the same time however long either chain is.
+    /// The storage of `self` is kept, along with its arena.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let render = Morphism::new::<u64>().tail(|x| x * 2).tail(|x| x.to_string());
+    /// let parse = Morphism::new::<&str>().tail(str::trim).tail(|s| s.len() as u64);
+    /// let f = render.then_front(parse);
+    /// assert_eq!(f.len(), 4);
+    /// assert_eq!(f.run(" abc "), "6");
+    /// ```
+    #[inline]
+    pub fn then_front<Z>(self, other: Morphism<'a, Z, A>) -> Morphism<'a, Z, B>
+        where A: Outlives<'a>,
+    {
+        let mut chain = self.chain;
+        chain.in_place = false;
+        let mut prefix = *other.chain;
+        let ends = other.ends.then(&mut prefix, self.ends);
+        chain.splice_front(prefix);
+        Morphism {
+            chain,
+            ends,
+        }
+    }
+
+    /// Compose one `Morphism` with another in reverse, running `other`
+    /// first. Equivalent to `other.then(self)`, and to
+    /// `self.then_front(other)`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let render = Morphism::new::<u64>().tail(|x| x.to_string());
+    /// let parse = Morphism::new::<&str>().tail(|s| s.len() as u64);
+    /// assert_eq!(render.after(parse).run("abc"), "3");
+    /// ```
+    #[inline]
+    pub fn after<Z>(self, other: Morphism<'a, Z, A>) -> Morphism<'a, Z, B>
+        where A: Outlives<'a>,
+    {
+        self.then_front(other)
+    }
+
+    /// Given an argument, run the chain of closures in a loop and return the
+    /// final result.
+    #[inline]
+    pub fn run(&self, x: A) -> B {
+        self.ends.run(&self.chain, x)
+    }
+
+    /// Run the chain on a clone of a borrowed argument.
+    ///
+    /// This is `run(x.clone())`. To avoid the clone, start the chain with
+    /// `head_ref` instead, and pass the reference to `run`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let words = vec!["a".to_string(), "bc".to_string()];
+    /// let f = Morphism::new::<String>().tail(|s| s + "!");
+    /// let g = Morphism::new::<&String>().tail(|s| s.len());
+    /// for w in &words {
+    ///     assert_eq!(f.run_ref(w).len(), g.run(w) + 1);
+    /// }
+    /// ```
+    #[inline]
+    pub fn run_ref(&self, x: &A) -> B
+        where A: Clone,
+    {
+        self.run(x.clone())
+    }
+
+    /// Like `run`, but if one of the closures panics, catch the panic and
+    /// return its payload instead of unwinding into the caller.
+    ///
+    /// The argument and any values in flight are dropped, and the chain
+    /// can be run again afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<u64>().tail(|x| 100 / x);
+    /// assert!(f.try_run(0).is_err());
+    /// assert_eq!(f.try_run(5).ok(), Some(20));
+    /// ```
+    pub fn try_run(&self, x: A) -> Result<B, Box<dyn Any + Send>> {
+        panic::catch_unwind(AssertUnwindSafe(|| self.run(x)))
+    }
+}
+
+// Combinators that embed a whole chain as a single step of a new one.
+// Running the outer chain runs the inner one from that step, which only
+// ever nests one level deep.
+impl<'a, A: 'a, B: 'a> Morphism<'a, A, B> {
+    /// Run the chain on the first component of a pair, passing the second
+    /// one through unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<u64>().tail(|x| x + 1).first::<&str>();
+    /// assert_eq!(f.run((41, "id")), (42, "id"));
+    /// ```
+    #[inline]
+    pub fn first<C: 'a>(self) -> Morphism<'a, (A, C), (B, C)> {
+        Morphism::new().tail(move |(a, c)| (self.run(a), c))
+    }
+
+    /// Run the chain on the second component of a pair, passing the
+    /// first one through unchanged.
+    #[inline]
+    pub fn second<C: 'a>(self) -> Morphism<'a, (C, A), (C, B)> {
+        Morphism::new().tail(move |(c, a)| (c, self.run(a)))
+    }
+
+    /// Run two chains side by side on the components of a pair
+    /// (`***` in Haskell's `Control.Arrow`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<u64>().tail(|x| x + 1);
+    /// let g = Morphism::new::<&str>().tail(|s| s.len());
+    /// assert_eq!(f.split(g).run((41, "abc")), (42, 3));
+    /// ```
+    #[inline]
+    pub fn split<C: 'a, D: 'a>(self, other: Morphism<'a, C, D>) -> Morphism<'a, (A, C), (B, D)> {
+        Morphism::new().tail(move |(a, c)| (self.run(a), other.run(c)))
+    }
+
+    /// Run two chains on the same argument, and pair up their results
+    /// (`&&&` in Haskell's `Control.Arrow`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<u64>().tail(|x| x + 1);
+    /// let g = Morphism::new::<u64>().tail(|x| x.to_string());
+    /// assert_eq!(f.fanout(g).run(41), (42, "41".to_string()));
+    /// ```
+    #[inline]
+    pub fn fanout<D: 'a>(self, other: Morphism<'a, A, D>) -> Morphism<'a, A, (B, D)>
+        where A: Clone,
+    {
+        Morphism::new().tail(move |a: A| (self.run(a.clone()), other.run(a)))
+    }
+
+    /// Run the chain on `Left` values, passing `Right` ones through
+    /// unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::either::Either;
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<u64>().tail(|x| x + 1).left::<&str>();
+    /// assert_eq!(f.run(Either::Left(41)), Either::Left(42));
+    /// assert_eq!(f.run(Either::Right("x")), Either::Right("x"));
+    /// ```
+    #[inline]
+    pub fn left<C: 'a>(self) -> Morphism<'a, Either<A, C>, Either<B, C>> {
+        Morphism::new().tail(move |x| match x {
+            Either::Left(a) => Either::Left(self.run(a)),
+            Either::Right(c) => Either::Right(c)
+        })
+    }
+
+    /// Run the chain on `Right` values, passing `Left` ones through
+    /// unchanged.
+    #[inline]
+    pub fn right<C: 'a>(self) -> Morphism<'a, Either<C, A>, Either<C, B>> {
+        Morphism::new().tail(move |x| match x {
+            Either::Left(c) => Either::Left(c),
+            Either::Right(a) => Either::Right(self.run(a))
+        })
+    }
+
+    /// Run the chain on `Ok` values, passing errors through unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<u64>().tail(|x| x + 1).ok::<String>();
+    /// assert_eq!(f.run(Ok(41)), Ok(42));
+    /// ```
+    #[inline]
+    pub fn ok<E: 'a>(self) -> Morphism<'a, Result<A, E>, Result<B, E>> {
+        Morphism::new().tail(move |x: Result<A, E>| x.map(|a| self.run(a)))
+    }
+
+    /// Run the chain on errors, passing `Ok` values through unchanged.
+    #[inline]
+    pub fn err<C: 'a>(self) -> Morphism<'a, Result<C, A>, Result<C, B>> {
+        Morphism::new().tail(move |x: Result<C, A>| x.map_err(|a| self.run(a)))
+    }
+
+    /// Continue the chain with `yes` when `pred` holds for the value
+    /// produced so far, and with `no` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<i64>().branch(
+    ///     |x| *x < 0,
+    ///     Morphism::new().tail(|x: i64| format!("minus {}", -x)),
+    ///     Morphism::new().tail(|x: i64| x.to_string()),
+    /// );
+    /// assert_eq!(f.run(-1), "minus 1");
+    /// assert_eq!(f.run(1), "1");
+    /// ```
+    #[inline]
+    pub fn branch<C: 'a, P>(self, pred: P, yes: Morphism<'a, B, C>, no: Morphism<'a, B, C>) -> Morphism<'a, A, C>
+        where P: Fn(&B) -> bool + 'a,
+    {
+        self.tail(move |b| if pred(&b) { yes.run(b) } else { no.run(b) })
+    }
+}
+
+impl<'a, A: 'a> Morphism<'a, A> {
+    /// Run the chain over and over, feeding each result back in, until
+    /// `pred` holds for the current value. The predicate is checked before
+    /// every run, so the chain may not run at all.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<u64>().tail(|x| x * 2).iterate_until(|x| *x > 100);
+    /// assert_eq!(f.run(3), 192);
+    /// assert_eq!(f.run(200), 200);
+    /// ```
+    #[inline]
+    pub fn iterate_until<P>(self, pred: P) -> Morphism<'a, A>
+        where P: Fn(&A) -> bool + 'a,
+    {
+        Morphism::new().tail(move |mut a| {
+            while !pred(&a) {
+                a = self.run(a);
+            }
+            a
+        })
+    }
+
+    /// Drop all closures, turning the chain back into the identity.
+    ///
+    /// The room the chain had for closures is kept, as is the arena or
+    /// allocator it was created with; use `shrink_to_fit` afterwards to
+    /// release the room too.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let mut f = Morphism::new::<u64>().tail(|x| x + 1).head(|x| x * 2);
+    /// f.clear();
+    /// assert!(f.is_identity());
+    /// assert_eq!(f.run(7), 7);
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.chain.front.clear();
+        self.chain.middle.clear();
+        self.chain.back.clear();
+        self.chain.joins = 0;
+        self.chain.fused = 0;
+        self.chain.in_place = true;
+        self.ends = Ends::identity();
+    }
+
+    /// Run the chain `n` times, feeding each result back in.
+    #[inline]
+    pub fn iterate_n(self, n: usize) -> Morphism<'a, A> {
+        Morphism::new().tail(move |a| (0..n).fold(a, |a, _| self.run(a)))
+    }
+
+    /// Split the chain in two, keeping the first `at` closures and
+    /// returning the rest as a chain of their own.
+    ///
+    /// A chain can always be split at either end. Between two closures,
+    /// it can only be split if every closure was attached with
+    /// `push_back`, `push_front`, `extend` or `collect`: after `head`,
+    /// `tail` or `then`, the type passed from one closure to the next is
+    /// no longer known. Otherwise, `None` is returned and the chain is
+    /// left as it was.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > self.len()`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let mut f: Morphism<u64> = (1..=3).map(|i| move |x| x * 10 + i).collect();
+    /// let g = f.split_off(1).unwrap();
+    /// assert_eq!((f.len(), g.len()), (1, 2));
+    /// assert_eq!(f.run(4), 41);
+    /// assert_eq!(g.run(4), 423);
+    ///
+    /// let mut h = Morphism::new::<u64>().tail(|x| x + 1).tail(|x| x * 2);
+    /// assert!(h.split_off(1).is_none());
+    /// assert_eq!(h.split_off(0).unwrap().run(4), 10);
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> Option<Morphism<'a, A>> {
+        let len = self.len();
+        assert!(at <= len, "cannot split a chain of {} closures at {}", len, at);
+        if at == len {
+            // Keeps the arena or allocator, unlike `Morphism::new()`.
+            let rest = self.chain.split_off(self.chain.len());
+            return Some(Morphism {
+                chain: Box::new(rest),
+                ends: Ends::identity(),
+            });
+        }
+        if at == 0 {
+            let mut rest = self.split_off(len)?;
+            mem::swap(self, &mut rest);
+            return Some(rest);
+        }
+        if !self.chain.in_place {
+            return None;
+        }
+        Some(Morphism {
+            chain: Box::new(self.chain.split_off(at)),
+            ends: self.ends.clone(),
+        })
+    }
+
+    /// Remove the last `n` closures, and return them as a chain of their
+    /// own, like `split_off(len - n)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > self.len()`.
+    pub fn drain_back(&mut self, n: usize) -> Option<Morphism<'a, A>> {
+        let len = self.len();
+        assert!(n <= len, "cannot drain {} closures from a chain of {}", n, len);
+        self.split_off(len - n)
+    }
+}
+
+impl<'a, A, B> Morphism<'a, A, B> {
+    /// The number of closures in the chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<u64>().tail(|x| x + 1);
+    /// assert_eq!(f.len(), 1);
+    /// assert_eq!(f.then(Morphism::new()).len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.chain.closures()
+    }
+
+    /// Whether the chain has no closures.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether running the chain returns its argument unchanged, which
+    /// is the case exactly when no closures have been attached to it.
+    #[inline]
+    pub fn is_identity(&self) -> bool {
+        self.is_empty()
+    }
+
+    /// An estimate of the heap memory held by the chain, in bytes.
+    ///
+    /// This counts the storage of the chain and the closures in it, but
+    /// not memory that the closures own indirectly. Closures shared with
+    /// clones of the chain are counted in full for each of them, and
+    /// closures allocated from an arena are not counted at all.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let f = Morphism::new::<u64>();
+    /// let buf = [0u8; 1024];
+    /// let g = f.tail(move |x| x + buf.len() as u64);
+    /// assert!(g.approx_heap_bytes() > 1024);
+    /// ```
+    pub fn approx_heap_bytes(&self) -> usize {
+        let middle: usize = self.chain.middle.iter().map(|segment| segment.steps.capacity()).sum();
+        let steps = self.chain.front.capacity() + middle + self.chain.back.capacity();
+        let closures: usize = self.chain.steps().map(|step| match step.f {
+            // An `Rc` keeps its two counts next to the value.
+            Closure::Shared(ref f) => 2 * mem::size_of::<usize>() + mem::size_of_val(&**f),
+            #[cfg(feature = "bumpalo")]
+            Closure::Arena(_) => 0,
+            #[cfg(feature = "allocator-api")]
+            Closure::Pooled(ref f) => 2 * mem::size_of::<usize>() + mem::size_of_val(&**f),
+        }).sum();
+        let segments = self.chain.middle.capacity() * mem::size_of::<Segment<'_>>();
+        mem::size_of::<Chain<'_>>() + segments + steps * mem::size_of::<Step<'_>>() + closures
+    }
+
+    /// Reserve room for at least `additional` more closures attached with
+    /// `tail` or `push_back`, so that building a chain of known length
+    /// does not reallocate along the way.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let mut f = Morphism::new::<u64>();
+    /// f.reserve(100);
+    /// for _ in 0..100 {
+    ///     f.push_back(|x| x + 1);
+    /// }
+    /// assert_eq!(f.run(0), 100);
+    /// ```
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.chain.back.reserve(additional)
+    }
+
+    /// Fuse each closure attached with `head` or `tail` into the step
+    /// next to it, as long as the chain holds fewer than `threshold`
+    /// closures. Named closures, and those attached with `push_front`
+    /// or `push_back`, are always kept as steps of their own.
+    ///
+    /// Fusing is off by default, as a fused step nests the dynamic
+    /// calls of the closures in it rather than saving any, and usually
+    /// runs slower than the same closures in separate steps:
+    /// `cargo bench --bench morphism` compares the two.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::Morphism;
+    ///
+    /// let mut f = Morphism::new::<u64>();
+    /// f.fuse_below(2);
+    /// let f = f.tail(|x| x + 1).tail(|x| x * 2).tail(|x| x - 3);
+    /// assert_eq!(f.len(), 3);
+    /// assert_eq!(format!("{:?}", f), "Morphism[_, _, _]");
+    /// assert_eq!(f.run(4), 7);
+    /// ```
+    #[inline]
+    pub fn fuse_below(&mut self, threshold: usize) {
+        self.chain.fuse_below = threshold;
+    }
+
+    /// Release any room for closures that the chain is not using.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.chain.front.shrink_to_fit();
+        self.chain.middle.shrink_to_fit();
+        self.chain.back.shrink_to_fit();
+    }
+}
+
+/// Assemble an endo-chain from closures, in the order they are to run.
+///
+/// # Example
+///
+/// ```rust
+/// use coyoneda::morphism::Morphism;
+///
+/// let steps: Vec<Box<dyn Fn(u64) -> u64>> = vec![Box::new(|x| x + 1), Box::new(|x| x * 2)];
+/// let f: Morphism<u64> = steps.into_iter().collect();
+/// assert_eq!(f.run(1), 4);
+/// ```
+impl<'a, A: 'a, F: Fn(A) -> A + 'a> FromIterator<F> for Morphism<'a, A> {
+    fn from_iter<I: IntoIterator<Item=F>>(iter: I) -> Morphism<'a, A> {
+        let mut f = Morphism::new();
+        f.extend(iter);
+        f
+    }
+}
+
+/// Push closures onto the back of the chain, like `push_back`.
+impl<'a, A, B: Outlives<'a>, F: Fn(B) -> B + 'a> Extend<F> for Morphism<'a, A, B> {
+    fn extend<I: IntoIterator<Item=F>>(&mut self, iter: I) {
+        for f in iter {
+            self.push_back(f);
+        }
+    }
+}
+
+/// `f >> g` composes `f` with `g`, like `f.then(g)`.
+impl<'a, A, B: Outlives<'a>, C: Outlives<'a>> Shr<Morphism<'a, B, C>> for Morphism<'a, A, B> {
+    type Output = Morphism<'a, A, C>;
+
+    #[inline]
+    fn shr(self, other: Morphism<'a, B, C>) -> Morphism<'a, A, C> {
+        self.then(other)
+    }
+}
+
+/// `g << f` composes `f` with `g`, like `f.then(g)`.
+impl<'a, A, B: Outlives<'a>, C: Outlives<'a>> Shl<Morphism<'a, A, B>> for Morphism<'a, B, C> {
+    type Output = Morphism<'a, A, C>;
+
+    #[inline]
+    fn shl(self, other: Morphism<'a, A, B>) -> Morphism<'a, A, C> {
+        other.then(self)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'a, A, B: 'a> FnOnce<(A,)> for Morphism<'a, A, B> {
+    type Output = B;
+
+    extern "rust-call" fn call_once(self, (x,): (A,)) -> B {
+        self.run(x)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'a, A, B: 'a> FnMut<(A,)> for Morphism<'a, A, B> {
+    extern "rust-call" fn call_mut(&mut self, (x,): (A,)) -> B {
+        self.run(x)
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<'a, A, B: 'a> Fn<(A,)> for Morphism<'a, A, B> {
+    extern "rust-call" fn call(&self, (x,): (A,)) -> B {
+        self.run(x)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::Morphism;
+    use crate::either::Either;
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn readme() {
+        let mut f = Morphism::new::<u64>();
+        for _ in 0..100000u64 {
+            f = f.tail(|x| x + 42u64);
+        }
+
+        let mut g = Morphism::new::<Option<u64>>();
+        for _ in 0..99999u64 {
+            g = g.tail(|x| x.map(|y| y - 42u64));
+        }
+
+        // type becomes Morphism<u64, (Option<u64>, bool, String)> so rebind g
+        let g = g
+            .tail(|x| (x.map(|y| y + 1000u64), "welp".to_string()))
+            .tail(|(l, r)| (l.map(|y| y + 42u64), r))
+            .tail(|(l, r)| (l, l.is_some(), r))
+            .head(Some);
+
+        let h = f.then(g);
+
+        assert_eq!(h.run(0u64), (Some(1084), true, "welp".to_string()));
+        assert_eq!(h.run(1000u64), (Some(2084), true, "welp".to_string()));
+    }
+
+    #[test]
+    fn large_and_aligned_values() {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[repr(align(64))]
+        struct Aligned(u8);
+
+        #[derive(Debug, PartialEq)]
+        #[repr(align(128))]
+        struct Empty;
+
+        let f = Morphism::new::<u8>()
+            .tail(|x| [u64::from(x); 32])
+            .tail(|xs| Aligned(xs.iter().sum::<u64>() as u8))
+            .tail(|x| (x, Empty))
+            .tail(|(x, _)| x.0.to_string());
+        assert_eq!(f.run(3), "96");
+        assert_eq!(Morphism::new::<Empty>().run(Empty), Empty);
+        assert_eq!(Morphism::new::<Aligned>().run(Aligned(7)), Aligned(7));
+    }
+
+    #[test]
+    #[cfg(feature = "unsafe-morphism")]
+    fn short_lived_values() {
+        fn words<'x>() -> Morphism<'static, &'x str, usize> {
+            Morphism::new::<&'x str>()
+                .tail(str::trim)
+                .tail(|s| s.split(' ').count())
+        }
+
+        let s = String::from(" a b c ");
+        assert_eq!(words().run(&s), 3);
+    }
+
+    #[test]
+    fn panicking_step() {
+        use std::panic::{self, AssertUnwindSafe};
+        use std::rc::Rc;
+
+        let counted = Rc::new(());
+        let f = Morphism::new::<Rc<()>>()
+            .tail(|x| vec![x])
+            .tail(|_| -> Rc<()> { panic!("step failed") });
+        let res = panic::catch_unwind(AssertUnwindSafe(|| f.run(counted.clone())));
+        assert!(res.is_err());
+        assert_eq!(Rc::strong_count(&counted), 1);
+    }
+
+    #[test]
+    fn try_run() {
+        let f = Morphism::new::<String>()
+            .tail(|s| s.parse::<u64>().unwrap())
+            .tail(|x| x.to_string() + "!");
+        let err = f.try_run("x".to_string()).unwrap_err();
+        assert!(err.downcast_ref::<String>().unwrap().contains("ParseIntError"));
+        assert_eq!(f.try_run("42".to_string()).unwrap(), "42!");
+        assert_eq!(f.run("7".to_string()), "7!");
+    }
+
+    #[cfg(feature = "bumpalo")]
+    #[test]
+    fn arena() {
+        use bumpalo::Bump;
+
+        let arena = Bump::new();
+        let mut f = Morphism::new_in::<u64>(&arena);
+        for i in 0..100u64 {
+            f = f.tail(move |x| x + i).head(|x: u64| x + 1);
+        }
+        let g = Morphism::new::<u64>().tail(|x| x.to_string());
+        assert_eq!(f.then(g).run(0), "5050");
+        assert!(arena.allocated_bytes() > 0);
+    }
+
+    #[cfg(feature = "allocator-api")]
+    #[test]
+    fn allocator() {
+        use std::alloc::Global;
+        use std::rc::Rc;
+
+        let counted = Rc::new(());
+        {
+            let mut f = Morphism::new_in_allocator::<u64>(&Global);
+            for i in 0..100u64 {
+                let c = counted.clone();
+                f = f.tail(move |x| { let _ = &c; x + i }).head(|x: u64| x + 1);
+            }
+            let g = Morphism::new::<u64>().tail(|x| x.to_string());
+            assert_eq!(f.then(g).run(0), "5050");
+        }
+        assert_eq!(Rc::strong_count(&counted), 1);
+    }
+
+    #[test]
+    fn then_front_and_back() {
+        for n in 0..12u64 {
+            for m in 0..12u64 {
+                let mut f = Morphism::new::<Vec<u64>>();
+                for i in 0..n {
+                    f = f.tail(move |mut v| { v.push(i); v });
+                }
+                let mut g = Morphism::new::<Vec<u64>>();
+                for i in 0..m {
+                    g = g.head(move |mut v: Vec<u64>| { v.push(n + i); v });
+                }
+                let expected: Vec<u64> = (0..n).chain((n..n + m).rev()).collect();
+                assert_eq!(f.then(g).run(Vec::new()), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn then_front_and_splices() {
+        fn piece(from: u64, n: u64) -> Morphism<'static, Vec<u64>> {
+            let mut f = Morphism::new();
+            for i in 0..n {
+                f = f.tail(move |mut v: Vec<u64>| { v.push(from + i); v });
+            }
+            f
+        }
+        // Grow a chain from both ends, with pieces shorter and longer
+        // than it, and compare with the order the values are expected in.
+        let mut f = piece(0, 0);
+        let mut expected = Vec::new();
+        let mut next = 0;
+        for round in 0..40u64 {
+            let n = (round * 7) % 13;
+            let g = piece(next, n);
+            let values: Vec<u64> = (next..next + n).collect();
+            next += n;
+            f = match round % 5 {
+                0 | 3 => {
+                    expected.extend(values);
+                    f.then(g)
+                }
+                1 => {
+                    expected.splice(0..0, values);
+                    f.then_front(g)
+                }
+                2 => {
+                    expected.splice(0..0, values);
+                    f.after(g.clone())
+                }
+                _ => {
+                    let v = next;
+                    next += 2;
+                    expected.insert(0, v);
+                    expected.push(v + 1);
+                    let mut f = f.head(move |mut w: Vec<u64>| { w.push(v); w });
+                    f.push_back(move |mut w| { w.push(v + 1); w });
+                    f
+                }
+            };
+            assert_eq!(f.len(), expected.len());
+            assert_eq!(f.clone().run(Vec::new()), expected);
+        }
+        assert!(!f.chain.middle.is_empty());
+        let mut g = f.clone();
+        assert!(g.split_off(1).is_none());
+        let h = g.split_off(0).unwrap();
+        assert_eq!(h.run(Vec::new()), expected);
+        assert!(g.is_identity());
+
+        let named = Morphism::new::<u64>().tail_named("inc", |x| x + 1);
+        let long = Morphism::new::<u64>().tail(|x| x * 2).tail(|x| x * 3).tail_named("dec", |x| x - 1);
+        let f = named.clone().then_front(long.clone()).then(long).then_front(named);
+        assert_eq!(format!("{:?}", f), r#"Morphism["inc", _, _, "dec", "inc", _, _, "dec"]"#);
+        assert_eq!(f.run(1), 71);
+    }
+
+    #[test]
+    fn clone_and_extend() {
+        let f = Morphism::new::<u64>().tail(|x| x + 1).tail(|x| x * 2);
+        let g = f.clone().tail(|x| x.to_string());
+        let mut h = f.clone();
+        h.push_front(|x| x + 10);
+        assert_eq!(f.run(1), 4);
+        assert_eq!(g.run(1), "4");
+        assert_eq!(h.run(1), 24);
+
+        // Runs of clones may nest.
+        let inner = f.clone();
+        let outer = f.tail(move |x| inner.run(x) + 1);
+        assert_eq!(outer.run(1), 11);
+    }
+
+    #[test]
+    fn default_is_identity() {
+        #[derive(Default)]
+        struct Pipeline<'a> {
+            normalize: Morphism<'a, String>,
+        }
+
+        let mut p = Pipeline::default();
+        assert_eq!(p.normalize.run("Abc ".to_string()), "Abc ");
+        p.normalize.push_back(|s| s.trim().to_string());
+        p.normalize.push_back(|s| s.to_lowercase());
+        assert_eq!(p.normalize.run("Abc ".to_string()), "abc");
+    }
+
+    #[test]
+    fn collect_and_extend() {
+        let steps: Vec<Box<dyn Fn(String) -> String>> = vec![
+            Box::new(|s| s.trim().to_string()),
+            Box::new(|s| s.to_uppercase()),
+        ];
+        let mut f: Morphism<String> = steps.into_iter().collect();
+        assert_eq!(f.run(" abc ".to_string()), "ABC");
+        f.extend((0..3).map(|i| move |s: String| s + &i.to_string()));
+        assert_eq!(f.run(" abc ".to_string()), "ABC012");
+    }
+
+    #[test]
+    fn len_and_identity() {
+        let f = Morphism::new::<u64>();
+        assert!(f.is_identity());
+        let f = f.then(Morphism::new()).then(Morphism::new());
+        assert!(f.is_empty());
+        let mut f = f.tail(|x| x + 1).then(Morphism::new().tail(|x| x * 2));
+        f.push_front(|x| x - 1);
+        assert_eq!(f.len(), 3);
+        assert!(!f.is_identity());
+        assert_eq!((f.clone() >> f).len(), 6);
+    }
+
+    #[test]
+    fn run_by_reference() {
+        let inputs = vec![vec![1u64, 2], vec![3]];
+        let f = Morphism::new::<Vec<u64>>().tail(|v| v.into_iter().sum::<u64>());
+        let g = Morphism::new::<&Vec<u64>>().tail(|v| v.iter().sum::<u64>());
+        for v in &inputs {
+            assert_eq!(f.run_ref(v), g.run(v));
+        }
+        assert_eq!(inputs.len(), 2);
+
+        struct NoClone(u64);
+        let x = NoClone(41);
+        let h = Morphism::new::<u64>()
+            .tail(|x| x + 1)
+            .head_ref(|x: &NoClone| x.0)
+            .head(|x: &NoClone| x);
+        assert_eq!(h.run(&x), 42);
+        assert_eq!(h.len(), 3);
+    }
+
+    #[test]
+    fn after_is_reversed_then() {
+        let f = Morphism::new::<String>().tail(|s| s + "f");
+        let g = Morphism::new::<String>().tail(|s| s + "g");
+        let mut h = Morphism::new::<String>();
+        for _ in 0..20 {
+            h.push_back(|s| s + ".");
+        }
+        assert_eq!(f.clone().after(g.clone()).run(String::new()), "gf");
+        assert_eq!(f.clone().after(h.clone()).run(String::new()).len(), 21);
+        assert_eq!(h.after(f).len(), 21);
+    }
+
+    #[test]
+    fn first_and_second() {
+        let f = Morphism::new::<u64>().tail(|x| x * 2).tail(|x| x.to_string());
+        let g = f.clone().first().then(f.second());
+        assert_eq!(g.run((21, 4)), ("42".to_string(), "8".to_string()));
+        assert_eq!(g.len(), 2);
+        let h = Morphism::new::<(u64, u64)>()
+            .then(Morphism::new().tail(|x: u64| x + 1).first())
+            .then(Morphism::new().tail(|x: u64| x - 1).second());
+        assert_eq!(h.run((1, 1)), (2, 0));
+    }
+
+    #[test]
+    fn split_and_fanout() {
+        let len = Morphism::new::<String>().tail(|s| s.len());
+        let upper = Morphism::new::<String>().tail(|s| s.to_uppercase());
+        let f = len.clone().fanout(upper.clone());
+        assert_eq!(f.run("ab".to_string()), (2, "AB".to_string()));
+        let g = len.split(upper).then(Morphism::new().tail(|(n, s): (usize, String)| s.repeat(n)));
+        assert_eq!(g.run(("abc".to_string(), "x".to_string())), "XXX");
+    }
+
+    #[test]
+    fn left_and_right() {
+        let parse = Morphism::new::<String>().tail(|s| s.parse::<u64>());
+        let double = Morphism::new::<u64>().tail(|x| x * 2);
+        let f = parse.then(double.clone().ok()).then(Morphism::new().tail(|r: Result<u64, _>| r.is_ok()));
+        assert!(f.run("21".to_string()));
+        assert!(!f.run("x".to_string()));
+        let g = double.clone().left().then(double.right());
+        assert_eq!(g.run(Either::Left(1)), Either::Left(2));
+        assert_eq!(g.run(Either::Right(2)), Either::Right(4));
+        let h = Morphism::new::<String>().tail(|s| s + "!").err::<u8>();
+        assert_eq!(h.run(Err("no".to_string())), Err("no!".to_string()));
+        assert_eq!(h.run(Ok(1)), Ok(1));
+    }
+
+    #[test]
+    fn branch_on_value() {
+        let mut collatz = Morphism::new::<u64>();
+        for _ in 0..5 {
+            collatz = collatz.branch(
+                |x| x % 2 == 0,
+                Morphism::new().tail(|x| x / 2),
+                Morphism::new().tail(|x| 3 * x + 1),
+            );
+        }
+        assert_eq!(collatz.run(6), 8);
+        assert_eq!(collatz.len(), 5);
+    }
+
+    #[test]
+    fn iterate() {
+        let step = Morphism::new::<(u64, u64)>().tail(|(a, b)| (b, a + b));
+        let fib = step.clone().iterate_n(10).tail(|(a, _)| a);
+        assert_eq!(fib.run((0, 1)), 55);
+        let f = step.iterate_until(|&(a, _)| a > 1000);
+        assert_eq!(f.run((0, 1)).0, 1597);
+        let g = Morphism::new::<u64>().tail(|x| x + 1).iterate_n(10000);
+        assert_eq!(g.len(), 1);
+        assert_eq!(g.run(0), 10000);
+    }
+
+    #[test]
+    fn split_off_and_drain_back() {
+        let mut f: Morphism<String> = (0..12).map(|i| move |s: String| s + &i.to_string()).collect();
+        f.push_front(|s| s + "<");
+        let mut g = f.split_off(5).unwrap();
+        assert_eq!((f.len(), g.len()), (5, 8));
+        assert_eq!(f.run(String::new()), "<0123");
+        assert_eq!(g.run(String::new()), "4567891011");
+        let h = g.drain_back(3).unwrap();
+        assert_eq!(g.run("a".to_string()), "a45678");
+        assert_eq!(h.run("b".to_string()), "b91011");
+        assert_eq!(h.then(f).run(String::new()), "91011<0123");
+        assert_eq!(g.drain_back(0).unwrap().run("c".to_string()), "c");
+        assert_eq!(g.split_off(0).unwrap().run("d".to_string()), "d45678");
+        assert!(g.is_identity());
+    }
+
+    #[test]
+    fn split_off_after_tail() {
+        let mut f = Morphism::new::<u64>().tail(|x| x as u8).tail(u64::from);
+        assert!(f.split_off(1).is_none());
+        assert!(f.drain_back(1).is_none());
+        assert_eq!((f.len(), f.run(300)), (2, 44));
+        assert!(f.drain_back(0).unwrap().is_identity());
+        let g = f.split_off(0).unwrap();
+        assert!(f.is_identity());
+        assert_eq!((g.len(), g.run(300)), (2, 44));
+    }
+
+    #[test]
+    fn debug_labels() {
+        let f = Morphism::new::<u64>()
+            .tail_named("inc", |x| x + 1)
+            .tail(|x| x * 2)
+            .head_named("parse", |s: &str| s.parse().unwrap());
+        let g = Morphism::new::<u64>().tail_named("render", |x| x.to_string());
+        let h = f.then(g);
+        assert_eq!(format!("{:?}", h), r#"Morphism["parse", "inc", _, "render"]"#);
+        assert_eq!(h.run("20"), "42");
+        assert_eq!(format!("{:?}", Morphism::new::<u64>()), "Morphism[]");
+    }
+
+    #[test]
+    fn fuse_below() {
+        let mut f = Morphism::new::<Vec<u64>>();
+        f.fuse_below(6);
+        let f = (0..4u64).fold(f, |f, i| f.tail(move |mut v| { v.push(i); v }));
+        let f = f.tail_named("five", |mut v| { v.push(5); v });
+        let f = (6..8u64).fold(f, |f, i| f.tail(move |mut v| { v.push(i); v }));
+        let f = f.head(|mut v: Vec<u64>| { v.push(9); v });
+        assert_eq!(f.chain.len(), 5);
+        assert_eq!(f.len(), 8);
+        assert_eq!(format!("{:?}", f), r#"Morphism[_, _, _, _, _, "five", _, _]"#);
+        assert_eq!(f.run(Vec::new()), [9, 0, 1, 2, 3, 5, 6, 7]);
+        let g = f.clone().tail(|v| v.len());
+        assert_eq!(f.then(Morphism::new().tail(|v: Vec<u64>| v.len())).len(), 9);
+        assert_eq!((g.len(), g.run(Vec::new())), (9, 8));
+
+        let mut h = Morphism::new::<u64>();
+        h.fuse_below(usize::MAX);
+        let mut h = h.tail(|x| x + 1).head(|x| x * 2).tail(|x| x * 3);
+        assert_eq!((h.chain.len(), h.len(), h.run(1)), (2, 3, 9));
+        h.clear();
+        assert_eq!((h.len(), h.run(1)), (0, 1));
+    }
+
+    #[test]
+    fn fused_macro() {
+        let id: Morphism<u64> = morphism!();
+        assert!(id.is_identity());
+        let suffix = "!".to_string();
+        let f = morphism!(|s: &str| s.len(), |n| n * 2, move |n: usize| n.to_string() + &suffix);
+        assert_eq!(f.len(), 1);
+        assert_eq!(f.run("abc"), "6!");
+        let g = morphism!(|x: u64| x + 1,).then(morphism!(|x: u64| x * 2));
+        assert_eq!(g.tail(|x| x - 1).run(1), 3);
+    }
+
+    #[test]
+    fn approx_heap_bytes() {
+        let f = Morphism::new::<Vec<u8>>();
+        let empty = f.approx_heap_bytes();
+        let buf = [0u8; 4096];
+        let f = f.tail(move |mut v| { v.extend_from_slice(&buf); v });
+        assert!(f.approx_heap_bytes() >= empty + 4096);
+        let mut g = f.clone();
+        for _ in 0..100 {
+            g.push_back(|v| v);
+        }
+        assert!(g.approx_heap_bytes() > f.approx_heap_bytes() + 100 * std::mem::size_of::<usize>());
+    }
+
+    #[test]
+    fn capacity() {
+        let mut f = Morphism::new::<u64>().tail(|x| x + 1);
+        f.reserve(100);
+        let cap = f.chain.back.capacity();
+        assert!(cap >= 101);
+        for _ in 0..99 {
+            f.push_back(|x| x + 1);
+        }
+        assert_eq!(f.chain.back.capacity(), cap);
+        let mut g = f.clone().then(Morphism::new::<u64>().head(|x| x * 2));
+        g.clear();
+        assert_eq!((g.len(), g.run(3)), (0, 3));
+        g.push_front(|x| x * 2);
+        assert_eq!(g.run(3), 6);
+        g.shrink_to_fit();
+        assert_eq!(g.chain.back.capacity(), 0);
+        assert_eq!(f.run(0), 100);
+    }
+
+    #[test]
+    fn compose_operators() {
+        let f = Morphism::new::<u64>().tail(|x| x + 1);
+        let g = Morphism::new::<u64>().tail(|x| x * 2);
+        let h = Morphism::new::<u64>().tail(|x| x.to_string());
+        assert_eq!((f.clone() >> g.clone() >> h.clone()).run(1), "4");
+        assert_eq!((h << f << g).run(1), "3");
+    }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn call_as_closure() {
+        let f = Morphism::new::<u64>().tail(|x| x + 1).tail(|x| x.to_string());
+        assert_eq!(f(41), "42");
+        let ys: Vec<String> = (0..3).map(&f).collect();
+        assert_eq!(ys, ["1", "2", "3"]);
+        let g = Morphism::new::<String>().tail(|s| s.len());
+        assert_eq!((0..3).map(f.then(g)).sum::<usize>(), 3);
+    }
+}
@@ -0,0 +1,121 @@
+//! Chains of `FnMut` closures.
+
+use super::Void;
+use super::points::Points;
+
+type Step<'a> = Box<dyn FnMut() + 'a>;
+
+/// A suspended chain of closures that may mutate their captured state,
+/// behaving as a stateful function from `A` to `B`.
+///
+/// Every call to `run` invokes each closure once, in order, so state
+/// such as counters or caches carries over from one run to the next.
+pub struct MutMorphism<'a, A, B = A> {
+    chain: Points<Step<'a>, A, B>,
+}
+
+impl MutMorphism<'static, Void> {
+    /// Create the identity chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::MutMorphism;
+    ///
+    /// assert_eq!(MutMorphism::new::<u64>().run(42u64), 42u64);
+    /// ```
+    #[inline]
+    pub fn new<'a, A>() -> MutMorphism<'a, A> {
+        MutMorphism {
+            chain: Points::new(),
+        }
+    }
+}
+
+impl<'a, B: 'a, C> MutMorphism<'a, B, C> {
+    /// Attach a closure to the front of the chain (pre-composition).
+    #[inline]
+    pub fn head<A: 'a, F>(self, mut f: F) -> MutMorphism<'a, A, C>
+        where F: FnMut(A) -> B + 'a,
+    {
+        MutMorphism {
+            chain: self.chain.head(|src, dst| Box::new(move || dst.set(src.take().map(&mut f)))),
+        }
+    }
+}
+
+impl<'a, A, B: 'a> MutMorphism<'a, A, B> {
+    /// Attach a closure to the back of the chain (post-composition).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::MutMorphism;
+    ///
+    /// let mut count = 0;
+    /// let mut f = MutMorphism::new::<u8>()
+    ///     .tail(move |x| { count += 1; (x, count) });
+    /// assert_eq!(f.run(7), (7, 1));
+    /// assert_eq!(f.run(7), (7, 2));
+    /// ```
+    #[inline]
+    pub fn tail<C: 'a, F>(self, mut f: F) -> MutMorphism<'a, A, C>
+        where F: FnMut(B) -> C + 'a,
+    {
+        MutMorphism {
+            chain: self.chain.tail(|src, dst| Box::new(move || dst.set(src.take().map(&mut f)))),
+        }
+    }
+
+    /// Compose one `MutMorphism` with another.
+    #[inline]
+    pub fn then<C: 'a>(self, other: MutMorphism<'a, B, C>) -> MutMorphism<'a, A, C> {
+        MutMorphism {
+            chain: self.chain.then(other.chain, |src, dst| Box::new(move || dst.set(src.take()))),
+        }
+    }
+
+    /// Given an argument, run the chain of closures in a loop, and
+    /// return the final result.
+    #[inline]
+    pub fn run(&mut self, x: A) -> B {
+        self.chain.put(x);
+        for f in self.chain.steps_mut() {
+            f();
+        }
+        self.chain.take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::MutMorphism;
+
+    #[test]
+    fn stateful_steps() {
+        let mut seen = HashSet::new();
+        let dedup = MutMorphism::new::<u32>()
+            .tail(move |x| if seen.insert(x) { Some(x) } else { None });
+        let mut f = MutMorphism::new::<&str>()
+            .head(|s: &str| s.trim())
+            .tail(|s| s.parse().unwrap())
+            .then(dedup);
+        assert_eq!(f.run(" 1"), Some(1));
+        assert_eq!(f.run("2 "), Some(2));
+        assert_eq!(f.run("1"), None);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn long_chain() {
+        let mut f = MutMorphism::new::<u64>();
+        for _ in 0..100000 {
+            let mut n = 0;
+            f = f.tail(move |x| { n += 1; x + n });
+        }
+        assert_eq!(f.run(0), 100000);
+        assert_eq!(f.run(0), 200000);
+    }
+}
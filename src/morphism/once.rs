@@ -0,0 +1,117 @@
+//! Chains of `FnOnce` closures.
+
+use super::Void;
+use super::points::{self, Points};
+
+type Step<'a> = Box<dyn FnOnce() + 'a>;
+
+/// A suspended chain of closures that can each be called only once,
+/// behaving as a function from `A` to `B` that is consumed by `run`.
+///
+/// Like `Morphism`, running the chain is a loop, so its length is only
+/// bounded by the heap. Unlike `Morphism`, the closures may move out of
+/// their captures.
+pub struct OnceMorphism<'a, A, B = A> {
+    chain: Points<Step<'a>, A, B>,
+}
+
+impl OnceMorphism<'static, Void> {
+    /// Create the identity chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::OnceMorphism;
+    ///
+    /// assert_eq!(OnceMorphism::new::<u64>().run(42u64), 42u64);
+    /// ```
+    #[inline]
+    pub fn new<'a, A>() -> OnceMorphism<'a, A> {
+        OnceMorphism {
+            chain: Points::new(),
+        }
+    }
+}
+
+impl<'a, B: 'a, C> OnceMorphism<'a, B, C> {
+    /// Attach a closure to the front of the chain (pre-composition).
+    #[inline]
+    pub fn head<A: 'a, F>(self, f: F) -> OnceMorphism<'a, A, C>
+        where F: FnOnce(A) -> B + 'a,
+    {
+        OnceMorphism {
+            chain: self.chain.head(|src, dst| Box::new(move || dst.set(src.take().map(f)))),
+        }
+    }
+}
+
+impl<'a, A, B: 'a> OnceMorphism<'a, A, B> {
+    /// Attach a closure to the back of the chain (post-composition).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::OnceMorphism;
+    ///
+    /// let buf = vec![1u8, 2, 3];
+    /// let f = OnceMorphism::new::<u8>()
+    ///     .tail(move |x| { let mut buf = buf; buf.push(x); buf })
+    ///     .tail(|buf| buf.len());
+    /// assert_eq!(f.run(4), 4);
+    /// ```
+    #[inline]
+    pub fn tail<C: 'a, F>(self, f: F) -> OnceMorphism<'a, A, C>
+        where F: FnOnce(B) -> C + 'a,
+    {
+        OnceMorphism {
+            chain: self.chain.tail(|src, dst| Box::new(move || dst.set(src.take().map(f)))),
+        }
+    }
+
+    /// Compose one `OnceMorphism` with another.
+    #[inline]
+    pub fn then<C: 'a>(self, other: OnceMorphism<'a, B, C>) -> OnceMorphism<'a, A, C> {
+        OnceMorphism {
+            chain: self.chain.then(other.chain, |src, dst| Box::new(move || dst.set(src.take()))),
+        }
+    }
+
+    /// Given an argument, run the chain of closures in a loop, consuming
+    /// it, and return the final result.
+    #[inline]
+    pub fn run(self, x: A) -> B {
+        self.chain.put(x);
+        let (steps, output) = self.chain.into_steps();
+        for f in steps {
+            f();
+        }
+        points::take(&output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OnceMorphism;
+
+    #[test]
+    fn move_captures() {
+        let prefix = String::from("n=");
+        let f = OnceMorphism::new::<u64>()
+            .tail(|x| x + 1)
+            .tail(move |x| prefix + &x.to_string())
+            .head(|s: &str| s.len() as u64);
+        let g = OnceMorphism::new::<String>().tail(|s| s + "!");
+        assert_eq!(f.then(g).run("abc"), "n=4!");
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn long_chain() {
+        let mut f = OnceMorphism::new::<u64>().tail(|x| x);
+        for i in 0..100000u64 {
+            let step = i.to_string();
+            f = f.tail(move |x| x + !step.is_empty() as u64);
+        }
+        assert_eq!(f.run(0), 100000);
+    }
+}
@@ -0,0 +1,107 @@
+//! Chains of closures that may yield nothing.
+
+use super::{
+    TryMorphism,
+    Void,
+};
+
+/// A suspended chain of closures that behave as a function from `A` to
+/// `Option<B>`.
+///
+/// Running the chain stops at the first closure that returns `None`, so
+/// the closures after it are never called.
+pub struct OptionMorphism<'a, A, B = A> {
+    chain: TryMorphism<'a, A, B, ()>,
+}
+
+impl OptionMorphism<'static, Void> {
+    /// Create the identity chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::OptionMorphism;
+    ///
+    /// assert_eq!(OptionMorphism::new::<u64>().run(42u64), Some(42u64));
+    /// ```
+    #[inline]
+    pub fn new<'a, A>() -> OptionMorphism<'a, A> {
+        OptionMorphism {
+            chain: TryMorphism::new(),
+        }
+    }
+}
+
+impl<'a, B: 'a, C> OptionMorphism<'a, B, C> {
+    /// Attach a closure to the front of the chain (pre-composition).
+    #[inline]
+    pub fn head<A: 'a, F>(self, f: F) -> OptionMorphism<'a, A, C>
+        where F: Fn(A) -> Option<B> + 'a,
+    {
+        OptionMorphism {
+            chain: self.chain.head(move |x| f(x).ok_or(())),
+        }
+    }
+}
+
+impl<'a, A, B: 'a> OptionMorphism<'a, A, B> {
+    /// Attach a closure to the back of the chain (post-composition).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::OptionMorphism;
+    ///
+    /// let f = OptionMorphism::new::<&str>()
+    ///     .tail(|s| s.strip_prefix("#"))
+    ///     .tail(|s| s.parse::<u8>().ok());
+    /// assert_eq!(f.run("#42"), Some(42));
+    /// assert_eq!(f.run("42"), None);
+    /// ```
+    #[inline]
+    pub fn tail<C: 'a, F>(self, f: F) -> OptionMorphism<'a, A, C>
+        where F: Fn(B) -> Option<C> + 'a,
+    {
+        OptionMorphism {
+            chain: self.chain.tail(move |x| f(x).ok_or(())),
+        }
+    }
+
+    /// Compose one `OptionMorphism` with another.
+    #[inline]
+    pub fn then<C: 'a>(self, other: OptionMorphism<'a, B, C>) -> OptionMorphism<'a, A, C> {
+        OptionMorphism {
+            chain: self.chain.then(other.chain),
+        }
+    }
+
+    /// Given an argument, run the chain of closures in a loop until one
+    /// of them yields `None`, and return the final result if there is
+    /// one.
+    #[inline]
+    pub fn run(&self, x: A) -> Option<B> {
+        self.chain.run(x).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::OptionMorphism;
+
+    #[test]
+    fn short_circuit() {
+        let calls = Cell::new(0);
+        let f = OptionMorphism::new::<u32>()
+            .tail(|x| x.checked_sub(1))
+            .tail(|x| { calls.set(calls.get() + 1); Some(x * 2) })
+            .head(|s: &str| s.parse().ok());
+        let g = OptionMorphism::new::<u32>().tail(|x| Some(x + 2));
+        let f = f.then(g);
+        assert_eq!(f.run("21"), Some(42));
+        assert_eq!(f.run("0"), None);
+        assert_eq!(f.run("x"), None);
+        assert_eq!(calls.get(), 1);
+    }
+}
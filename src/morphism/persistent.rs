@@ -0,0 +1,181 @@
+//! Chains that share their steps with the chains they were built from.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use super::Void;
+use super::points::Point;
+
+// A step, linked to the steps that run before it.
+struct Node<'a> {
+    f: Box<dyn Fn() + 'a>,
+    prev: Option<Rc<Node<'a>>>,
+}
+
+// Unlink the nodes one by one, so that dropping a long chain doesn't
+// recurse once for every step.
+impl<'a> Drop for Node<'a> {
+    fn drop(&mut self) {
+        let mut prev = self.prev.take();
+        while let Some(node) = prev {
+            match Rc::try_unwrap(node) {
+                Ok(mut node) => prev = node.prev.take(),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+/// A suspended chain of closures from `A` to `B` that is never changed
+/// once built.
+///
+/// Attaching a closure with `tail` leaves the chain as it is, and
+/// returns a new chain that links to it rather than copying its steps.
+/// Clones are as cheap as cloning an `Rc`, and any number of chains can
+/// branch off a shared prefix.
+///
+/// The steps are linked from the back, so running the chain first
+/// collects them in order, which allocates once per run.
+///
+/// # Example
+///
+/// ```rust
+/// use coyoneda::morphism::PersistentMorphism;
+///
+/// let prefix = PersistentMorphism::new::<&str>().tail(|s| s.trim().to_string());
+/// let upper = prefix.tail(|s| s.to_uppercase());
+/// let len = prefix.tail(|s| s.len());
+/// assert_eq!(upper.run(" abc "), "ABC");
+/// assert_eq!(len.run(" abc "), 3);
+/// ```
+pub struct PersistentMorphism<'a, A, B = A> {
+    // Each step takes its argument out of the point before it and puts
+    // its result into the point after it.
+    last: Option<Rc<Node<'a>>>,
+    len: usize,
+    input: Point<A>,
+    output: Point<B>,
+}
+
+impl PersistentMorphism<'static, Void> {
+    /// Create the identity chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::PersistentMorphism;
+    ///
+    /// assert_eq!(PersistentMorphism::new::<u64>().run(42u64), 42u64);
+    /// ```
+    #[inline]
+    pub fn new<'a, A>() -> PersistentMorphism<'a, A> {
+        let point = Rc::new(Cell::new(None));
+        PersistentMorphism {
+            last: None,
+            len: 0,
+            input: point.clone(),
+            output: point,
+        }
+    }
+}
+
+impl<'a, A, B: 'a> PersistentMorphism<'a, A, B> {
+    /// Return a new chain that runs this one, followed by `f`.
+    #[inline]
+    pub fn tail<C: 'a, F>(&self, f: F) -> PersistentMorphism<'a, A, C>
+        where F: Fn(B) -> C + 'a,
+    {
+        let output = Rc::new(Cell::new(None));
+        let (src, dst) = (self.output.clone(), output.clone());
+        let node = Node {
+            f: Box::new(move || dst.set(src.take().map(&f))),
+            prev: self.last.clone(),
+        };
+        PersistentMorphism {
+            last: Some(Rc::new(node)),
+            len: self.len + 1,
+            input: self.input.clone(),
+            output,
+        }
+    }
+
+    /// The number of closures in the chain.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the chain has no closures.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Given an argument, run the chain of closures in a loop and return
+    /// the final result.
+    pub fn run(&self, x: A) -> B {
+        let mut steps = Vec::with_capacity(self.len);
+        let mut node = self.last.as_ref();
+        while let Some(n) = node {
+            steps.push(&n.f);
+            node = n.prev.as_ref();
+        }
+        self.input.set(Some(x));
+        for f in steps.into_iter().rev() {
+            f();
+        }
+        match self.output.take() {
+            Some(y) => y,
+            None => unreachable!()
+        }
+    }
+}
+
+// Clones share their steps and points, which is fine as long as runs of
+// either one leave every point empty again.
+impl<'a, A, B> Clone for PersistentMorphism<'a, A, B> {
+    fn clone(&self) -> PersistentMorphism<'a, A, B> {
+        PersistentMorphism {
+            last: self.last.clone(),
+            len: self.len,
+            input: self.input.clone(),
+            output: self.output.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::PersistentMorphism;
+
+    #[test]
+    fn branch_off_prefix() {
+        let calls = Cell::new(0);
+        let prefix = PersistentMorphism::new::<u64>()
+            .tail(|x| { calls.set(calls.get() + 1); x + 1 });
+        let rules: Vec<PersistentMorphism<u64, String>> = (0..10u64)
+            .map(|i| prefix.tail(move |x| x * i).tail(|x| x.to_string()))
+            .collect();
+        for (i, rule) in rules.iter().enumerate() {
+            assert_eq!(rule.len(), 3);
+            assert_eq!(rule.run(1), (2 * i).to_string());
+        }
+        assert_eq!(prefix.clone().run(1), 2);
+        assert_eq!(calls.get(), 11);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn long_chain() {
+        let mut f = PersistentMorphism::new::<u64>();
+        for _ in 0..100000 {
+            f = f.tail(|x| x + 1);
+        }
+        let g = f.tail(|x| x * 2);
+        assert_eq!(f.run(0), 100000);
+        drop(f);
+        assert_eq!(g.run(0), 200000);
+    }
+}
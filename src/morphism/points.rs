@@ -0,0 +1,122 @@
+//! The chain underlying `OnceMorphism`, `MutMorphism`, `TryMorphism`
+//! and `AsyncMorphism`. They differ only in the type of their steps,
+//! `S`, and in how they call them.
+//!
+//! Every point between two steps is a shared cell. Each step takes its
+//! argument out of the cell before it, and puts its result into the
+//! cell after it.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+pub type Point<A> = Rc<Cell<Option<A>>>;
+
+pub struct Points<S, A, B> {
+    // Steps run as `front` in reverse, followed by `back`.
+    front: Vec<S>,
+    back: Vec<S>,
+    input: Point<A>,
+    output: Point<B>,
+}
+
+impl<S, A> Points<S, A, A> {
+    #[inline]
+    pub fn new() -> Points<S, A, A> {
+        let point = Rc::new(Cell::new(None));
+        Points {
+            front: Vec::new(),
+            back: Vec::new(),
+            input: point.clone(),
+            output: point,
+        }
+    }
+}
+
+impl<S, A, B> Points<S, A, B> {
+    // Push the step that `step` makes from a new input point and the
+    // current one onto the front.
+    #[inline]
+    pub fn head<Z, F>(self, step: F) -> Points<S, Z, B>
+        where F: FnOnce(Point<Z>, Point<A>) -> S,
+    {
+        let input = Rc::new(Cell::new(None));
+        let mut front = self.front;
+        front.push(step(input.clone(), self.input));
+        Points {
+            front,
+            back: self.back,
+            input,
+            output: self.output,
+        }
+    }
+
+    // Push the step that `step` makes from the current output point and
+    // a new one onto the back.
+    #[inline]
+    pub fn tail<C, F>(self, step: F) -> Points<S, A, C>
+        where F: FnOnce(Point<B>, Point<C>) -> S,
+    {
+        let output = Rc::new(Cell::new(None));
+        let mut back = self.back;
+        back.push(step(self.output, output.clone()));
+        Points {
+            front: self.front,
+            back,
+            input: self.input,
+            output,
+        }
+    }
+
+    // Append the steps of `other`, after the step that `join` makes to
+    // move values from the output point of `self` to the input point of
+    // `other`.
+    #[inline]
+    pub fn then<C, F>(self, other: Points<S, B, C>, join: F) -> Points<S, A, C>
+        where F: FnOnce(Point<B>, Point<B>) -> S,
+    {
+        let mut back = self.back;
+        back.push(join(self.output, other.input));
+        back.extend(other.front.into_iter().rev());
+        back.extend(other.back);
+        Points {
+            front: self.front,
+            back,
+            input: self.input,
+            output: other.output,
+        }
+    }
+
+    #[inline]
+    pub fn put(&self, x: A) {
+        self.input.set(Some(x));
+    }
+
+    #[inline]
+    pub fn steps(&self) -> impl Iterator<Item=&S> {
+        self.front.iter().rev().chain(self.back.iter())
+    }
+
+    #[inline]
+    pub fn steps_mut(&mut self) -> impl Iterator<Item=&mut S> {
+        self.front.iter_mut().rev().chain(self.back.iter_mut())
+    }
+
+    #[inline]
+    pub fn into_steps(self) -> (impl Iterator<Item=S>, Point<B>) {
+        (self.front.into_iter().rev().chain(self.back), self.output)
+    }
+
+    // Take the result out of the output point, once every step has run.
+    #[inline]
+    pub fn take(&self) -> B {
+        take(&self.output)
+    }
+}
+
+#[inline(always)]
+pub fn take<A>(point: &Point<A>) -> A {
+    match point.take() {
+        Some(x) => x,
+        None => unreachable!()
+    }
+}
@@ -0,0 +1,205 @@
+//! The fast path: values move from step to step through a single
+//! scratch slot, large and aligned enough for every type along the
+//! chain. Each step reads its argument out of the slot and writes its
+//! result back into it.
+//!
+//! Values are only ever moved with `ptr::read` and `ptr::write`, and the
+//! slot pointer is always derived from the allocation it points into,
+//! so provenance is never lost on the way. The only pointer made up
+//! from an integer is the dangling one used for zero-sized slots, which
+//! is never dereferenced. This keeps the backend clean under Miri with
+//! `-Zmiri-strict-provenance`:
+//!
+//! ```text
+//! MIRIFLAGS=-Zmiri-strict-provenance cargo +nightly miri test
+//! ```
+
+use std::alloc::{
+    self,
+    Layout,
+};
+use std::cmp::{
+    max,
+};
+use std::marker::{
+    PhantomData,
+};
+use std::mem::{
+    self,
+    MaybeUninit,
+};
+use std::ptr;
+
+use super::{Chain, Label};
+
+pub type Slot = *mut u8;
+
+pub struct Ends<A, B> {
+    slot: Layout,
+    phan: PhantomData<(A, B)>,
+}
+
+impl<A, B> Clone for Ends<A, B> {
+    fn clone(&self) -> Ends<A, B> {
+        Ends {
+            slot: self.slot,
+            phan: PhantomData,
+        }
+    }
+}
+
+impl<A> Ends<A, A> {
+    #[inline(always)]
+    pub fn identity() -> Ends<A, A> {
+        Ends {
+            slot: Layout::new::<A>(),
+            phan: PhantomData,
+        }
+    }
+}
+
+impl<A, B> Ends<A, B> {
+    // Reinterpret the ends at different types, growing the slot to fit
+    // `layout`. Only sound when the steps have just been changed to
+    // match them.
+    #[inline(always)]
+    fn cast<X, Y>(self, layout: Layout) -> Ends<X, Y> {
+        let size = max(self.slot.size(), layout.size());
+        let align = max(self.slot.align(), layout.align());
+        Ends {
+            // Both are at most `isize::MAX` and `align` is a power of two.
+            slot: unsafe { Layout::from_size_align_unchecked(size, align) },
+            phan: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    pub fn head<'a, Z, F>(self, chain: &mut Chain<'a>, label: Label, f: F) -> Ends<Z, B>
+        where F: Fn(Z) -> A + 'a,
+    {
+        chain.push_head(label, move |slot: Slot| unsafe {
+            let x = ptr::read(slot.cast::<Z>());
+            ptr::write(slot.cast::<A>(), f(x));
+        });
+        self.cast(Layout::new::<Z>())
+    }
+
+    #[inline(always)]
+    pub fn tail<'a, C, F>(self, chain: &mut Chain<'a>, label: Label, f: F) -> Ends<A, C>
+        where F: Fn(B) -> C + 'a,
+    {
+        chain.push_tail(label, move |slot: Slot| unsafe {
+            let x = ptr::read(slot.cast::<B>());
+            ptr::write(slot.cast::<C>(), f(x));
+        });
+        self.cast(Layout::new::<C>())
+    }
+
+    #[inline(always)]
+    pub fn push_front<'a, F>(&mut self, chain: &mut Chain<'a>, f: F)
+        where F: Fn(A) -> A + 'a,
+    {
+        chain.front.push(chain.step(move |slot: Slot| unsafe {
+            let x = ptr::read(slot.cast::<A>());
+            ptr::write(slot.cast::<A>(), f(x));
+        }));
+    }
+
+    #[inline(always)]
+    pub fn push_back<'a, F>(&mut self, chain: &mut Chain<'a>, f: F)
+        where F: Fn(B) -> B + 'a,
+    {
+        chain.back.push(chain.step(move |slot: Slot| unsafe {
+            let x = ptr::read(slot.cast::<B>());
+            ptr::write(slot.cast::<B>(), f(x));
+        }));
+    }
+
+    #[inline(always)]
+    pub fn then<C>(self, _: &mut Chain<'_>, other: Ends<B, C>) -> Ends<A, C> {
+        self.cast(other.slot)
+    }
+
+    #[inline(always)]
+    pub fn run(&self, chain: &Chain<'_>, x: A) -> B {
+        with_slot(self.slot, |slot| unsafe { run_in(chain, slot, x) })
+    }
+
+    // Like `run`, but take the slot from `scratch` when it doesn't fit on
+    // the stack, allocating it there on the first run only.
+    #[inline(always)]
+    pub fn run_with(&self, chain: &Chain<'_>, scratch: &mut Scratch, x: A) -> B {
+        if fits_inline(self.slot) {
+            return self.run(chain, x);
+        }
+        let slot = match scratch.0 {
+            Some(ref heap) if heap.1 == self.slot => heap.0,
+            _ => scratch.0.insert(Heap::new(self.slot)).0,
+        };
+        unsafe { run_in(chain, slot, x) }
+    }
+}
+
+// Move `x` into `slot`, run the steps of `chain` on it, and move the
+// result out. The slot has to fit every type along the chain.
+#[inline(always)]
+unsafe fn run_in<A, B>(chain: &Chain<'_>, slot: Slot, x: A) -> B {
+    ptr::write(slot.cast::<A>(), x);
+    for f in chain.steps() {
+        f(slot);
+    }
+    ptr::read(slot.cast::<B>())
+}
+
+// Memory for a slot that doesn't fit on the stack, kept from one run
+// to the next.
+#[derive(Default)]
+pub struct Scratch(Option<Heap>);
+
+const INLINE_SLOT: usize = 64;
+
+#[repr(C, align(16))]
+struct InlineSlot([MaybeUninit<u8>; INLINE_SLOT]);
+
+// Call `f` with a pointer to uninitialized memory fitting `layout`. The
+// memory lives on the stack when it is small enough. Whatever `f` leaves
+// in it is not dropped.
+#[inline(always)]
+fn with_slot<R, F: FnOnce(Slot) -> R>(layout: Layout, f: F) -> R {
+    if fits_inline(layout) {
+        let mut inline = InlineSlot([MaybeUninit::uninit(); INLINE_SLOT]);
+        return f(inline.0.as_mut_ptr().cast());
+    }
+    let heap = Heap::new(layout);
+    f(heap.0)
+}
+
+#[inline(always)]
+fn fits_inline(layout: Layout) -> bool {
+    layout.size() <= INLINE_SLOT && layout.align() <= mem::align_of::<InlineSlot>()
+}
+
+// Uninitialized memory fitting a layout, on the heap unless it has no
+// size. Whatever is left in it is not dropped.
+struct Heap(Slot, Layout);
+
+impl Heap {
+    fn new(layout: Layout) -> Heap {
+        if layout.size() == 0 {
+            return Heap(ptr::without_provenance_mut(layout.align()), layout);
+        }
+        let heap = unsafe { alloc::alloc(layout) };
+        if heap.is_null() {
+            alloc::handle_alloc_error(layout);
+        }
+        Heap(heap, layout)
+    }
+}
+
+impl Drop for Heap {
+    fn drop(&mut self) {
+        if self.1.size() != 0 {
+            unsafe { alloc::dealloc(self.0, self.1) }
+        }
+    }
+}
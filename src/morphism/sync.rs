@@ -0,0 +1,196 @@
+//! Chains of closures that can be shared between threads.
+
+use std::any::Any;
+use std::marker::PhantomData;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use super::Void;
+
+type Value = Box<dyn Any>;
+
+type Step<'a> = Box<dyn Fn(Value) -> Value + Send + Sync + 'a>;
+
+/// A suspended chain of `Send + Sync` closures that behave as a function
+/// from type `A` to type `B`.
+///
+/// A `SyncMorphism` can be built on one thread and run on others, also
+/// concurrently. Each run hands values from one step to the next in
+/// boxes of its own, so unlike `Morphism` every type along the chain
+/// has to be `'static`.
+pub struct SyncMorphism<'a, A, B = A> {
+    // Steps run as `front` in reverse, followed by `back`.
+    front: Vec<Step<'a>>,
+    back: Vec<Step<'a>>,
+    phan: PhantomData<fn(A) -> B>,
+}
+
+impl SyncMorphism<'static, Void> {
+    /// Create the identity chain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::SyncMorphism;
+    ///
+    /// assert_eq!(SyncMorphism::new::<u64>().run(42u64), 42u64);
+    /// ```
+    #[inline]
+    pub fn new<'a, A: 'static>() -> SyncMorphism<'a, A> {
+        SyncMorphism {
+            front: Vec::new(),
+            back: Vec::new(),
+            phan: PhantomData,
+        }
+    }
+}
+
+impl<'a, B: 'static, C> SyncMorphism<'a, B, C> {
+    /// Attach a closure to the front of the chain (pre-composition).
+    #[inline]
+    pub fn head<A: 'static, F>(self, f: F) -> SyncMorphism<'a, A, C>
+        where F: Fn(A) -> B + Send + Sync + 'a,
+    {
+        let mut front = self.front;
+        front.push(Box::new(move |x| Box::new(f(unbox(x)))));
+        SyncMorphism {
+            front,
+            back: self.back,
+            phan: PhantomData,
+        }
+    }
+}
+
+impl<'a, A, B: 'static> SyncMorphism<'a, A, B> {
+    /// Attach a closure to the back of the chain (post-composition).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::thread;
+    /// use coyoneda::morphism::SyncMorphism;
+    ///
+    /// let f = SyncMorphism::new::<u64>()
+    ///     .tail(|x| x + 1)
+    ///     .tail(|x| x.to_string());
+    /// let y = thread::scope(|s| s.spawn(|| f.run(41)).join().unwrap());
+    /// assert_eq!(y, "42");
+    /// ```
+    #[inline]
+    pub fn tail<C: 'static, F>(self, f: F) -> SyncMorphism<'a, A, C>
+        where F: Fn(B) -> C + Send + Sync + 'a,
+    {
+        let mut back = self.back;
+        back.push(Box::new(move |x| Box::new(f(unbox(x)))));
+        SyncMorphism {
+            front: self.front,
+            back,
+            phan: PhantomData,
+        }
+    }
+
+    /// Compose one `SyncMorphism` with another.
+    #[inline]
+    pub fn then<C>(self, other: SyncMorphism<'a, B, C>) -> SyncMorphism<'a, A, C> {
+        let mut back = self.back;
+        back.extend(other.front.into_iter().rev());
+        back.extend(other.back);
+        SyncMorphism {
+            front: self.front,
+            back,
+            phan: PhantomData,
+        }
+    }
+
+    /// Given an argument, run the chain of closures in a loop and return
+    /// the final result.
+    #[inline]
+    pub fn run(&self, x: A) -> B
+        where A: 'static,
+    {
+        let mut x: Value = Box::new(x);
+        for f in self.front.iter().rev().chain(self.back.iter()) {
+            x = f(x);
+        }
+        unbox(x)
+    }
+
+    /// Run the chain on a clone of every element of `xs`, spread across
+    /// the rayon thread pool, and collect the results in order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::morphism::SyncMorphism;
+    ///
+    /// let f = SyncMorphism::new::<u64>().tail(|x| x * 2);
+    /// let xs: Vec<u64> = (0..1000).collect();
+    /// let ys = f.par_map_slice(&xs);
+    /// assert_eq!(ys[999], 1998);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn par_map_slice(&self, xs: &[A]) -> Vec<B>
+        where A: Clone + Sync + 'static, B: Send,
+    {
+        xs.par_iter().map(|x| self.run(x.clone())).collect()
+    }
+}
+
+#[inline(always)]
+fn unbox<X: 'static>(x: Value) -> X {
+    match x.downcast() {
+        Ok(x) => *x,
+        Err(_) => unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    use super::SyncMorphism;
+
+    #[test]
+    fn run_on_threads() {
+        let calls = AtomicUsize::new(0);
+        let f = SyncMorphism::new::<usize>()
+            .tail(|x| { calls.fetch_add(1, Ordering::Relaxed); x * 2 })
+            .head(|s: String| s.len());
+        let g = SyncMorphism::new::<usize>().tail(|x| x + 1);
+        let h = f.then(g);
+        let ys: Vec<usize> = thread::scope(|s| {
+            let hs: Vec<_> = (0..4).map(|n| {
+                let h = &h;
+                s.spawn(move || h.run("x".repeat(n)))
+            }).collect();
+            hs.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(ys, vec![1, 3, 5, 7]);
+        assert_eq!(calls.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn long_chain() {
+        let mut f = SyncMorphism::new::<u64>();
+        for _ in 0..100000 {
+            f = f.tail(|x| x + 1);
+        }
+        assert_eq!(f.run(0), 100000);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_map_slice() {
+        let calls = AtomicUsize::new(0);
+        let f = SyncMorphism::new::<String>()
+            .tail(|s| s.len())
+            .tail(|n| { calls.fetch_add(1, Ordering::Relaxed); n * 2 });
+        let xs: Vec<String> = (0..100).map(|n| "x".repeat(n)).collect();
+        let ys = f.par_map_slice(&xs);
+        assert_eq!(ys, (0..100).map(|n| n * 2).collect::<Vec<_>>());
+        assert_eq!(calls.load(Ordering::Relaxed), 100);
+    }
+}
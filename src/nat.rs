@@ -0,0 +1,136 @@
+//! Natural transformations as values.
+//!
+//! `NaturalTransform` ties a transformation to the point type itself,
+//! which makes it impossible to choose between several transformations
+//! for the same pair of functors. A `Nat` is a value that knows how to
+//! convert a point `T` into a point `U` carrying the same parameter,
+//! and can be handed to `Coyoneda::hoist`.
+
+use std::marker::PhantomData;
+use std::rc::Rc;
+#[cfg(feature = "sync")]
+use std::sync::Arc;
+
+use crate::either::Either;
+use crate::functor::NaturalTransform;
+use crate::functor::parametric::Param;
+
+/// A transformation from the functor `T` to the functor `U`.
+///
+/// Implementations are usually generic over the parameter, so the same
+/// value can be used to hoist suspensions of any element type:
+///
+/// ```
+/// struct BoxToOption;
+///
+/// impl<A> Nat<Box<A>, Option<A>> for BoxToOption {
+///     fn apply(&self, x: Box<A>) -> Option<A> { Some(*x) }
+/// }
+/// ```
+pub trait Nat<T: Param, U: Param<Param=T::Param>> {
+    fn apply(&self, point: T) -> U;
+
+    /// Follow this transformation with another one, from `U` to `V`.
+    fn then<V, M>(self, other: M) -> ComposedNat<Self, M, U>
+        where Self: Sized, V: Param<Param=T::Param>, M: Nat<U, V> {
+        compose(self, other)
+    }
+}
+
+impl<T, U, F> Nat<T, U> for F
+    where T: Param, U: Param<Param=T::Param>, F: Fn(T) -> U {
+    fn apply(&self, point: T) -> U {
+        self(point)
+    }
+}
+
+/// The identity transformation, which leaves the point untouched.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Identity;
+
+impl<T: Param> Nat<T, T> for Identity {
+    fn apply(&self, point: T) -> T {
+        point
+    }
+}
+
+/// Lifts an existing `NaturalTransform` impl into a `Nat` value.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Transform;
+
+impl<T, U> Nat<T, U> for Transform
+    where T: NaturalTransform<U>, U: Param<Param=T::Param> {
+    fn apply(&self, point: T) -> U {
+        point.transform()
+    }
+}
+
+// The transformations below are not `NaturalTransform` impls: `Box`
+// and `Result` already have one, to `Option`, and a second would make
+// every existing `transform()` call on them ambiguous.
+
+/// Moves the value out of a `Box` into a new `Rc`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BoxToRc;
+
+impl<A> Nat<Box<A>, Rc<A>> for BoxToRc {
+    fn apply(&self, point: Box<A>) -> Rc<A> {
+        Rc::new(*point)
+    }
+}
+
+/// Moves the value out of a `Box` into `Ok`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BoxToOk;
+
+impl<A, E> Nat<Box<A>, Result<A, E>> for BoxToOk {
+    fn apply(&self, point: Box<A>) -> Result<A, E> {
+        Ok(*point)
+    }
+}
+
+/// Turns `Ok` into `Right` and `Err` into `Left`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ResultToEither;
+
+impl<A, E> Nat<Result<A, E>, Either<E, A>> for ResultToEither {
+    fn apply(&self, point: Result<A, E>) -> Either<E, A> {
+        match point {
+            Ok(a) => Either::Right(a),
+            Err(e) => Either::Left(e)
+        }
+    }
+}
+
+/// Moves the value out of a `Box` into a new `Arc`.
+#[cfg(feature = "sync")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BoxToArc;
+
+#[cfg(feature = "sync")]
+impl<A> Nat<Box<A>, Arc<A>> for BoxToArc {
+    fn apply(&self, point: Box<A>) -> Arc<A> {
+        Arc::new(*point)
+    }
+}
+
+/// The transformation `first` followed by `second`, passing through
+/// the intermediate functor `G`.
+pub struct ComposedNat<N, M, G> {
+    first: N,
+    second: M,
+    phan: PhantomData<fn() -> G>
+}
+
+impl<T, G, U, N, M> Nat<T, U> for ComposedNat<N, M, G>
+    where T: Param, G: Param<Param=T::Param>, U: Param<Param=T::Param>,
+          N: Nat<T, G>, M: Nat<G, U> {
+    fn apply(&self, point: T) -> U {
+        self.second.apply(self.first.apply(point))
+    }
+}
+
+/// Compose two transformations, from `T` to `G` and from `G` to `U`.
+pub fn compose<N, M, G>(first: N, second: M) -> ComposedNat<N, M, G> {
+    ComposedNat{first, second, phan: PhantomData}
+}
@@ -0,0 +1,151 @@
+//! A vector with at least one element.
+
+use std::iter;
+use std::slice;
+
+use crate::functor::{Comonad, Covariant, CovariantRef, Extract, Foldable, Traversable};
+use crate::functor::parametric::{Param, ReParam};
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NonEmpty<A> {
+    pub head: A,
+    pub tail: Vec<A>
+}
+
+impl<A> NonEmpty<A> {
+
+    pub fn new(head: A) -> NonEmpty<A> {
+        NonEmpty{head, tail: Vec::new()}
+    }
+
+    /// Returns `None` if the vector is empty.
+    pub fn from_vec(mut vec: Vec<A>) -> Option<NonEmpty<A>> {
+        if vec.is_empty() {
+            None
+        } else {
+            let head = vec.remove(0);
+            Some(NonEmpty{head, tail: vec})
+        }
+    }
+
+    pub fn push(&mut self, a: A) {
+        self.tail.push(a)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tail.len() + 1
+    }
+
+    /// Always `false`, provided for symmetry with `len`.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn first(&self) -> &A {
+        &self.head
+    }
+
+    pub fn last(&self) -> &A {
+        self.tail.last().unwrap_or(&self.head)
+    }
+
+    pub fn iter(&self) -> iter::Chain<iter::Once<&A>, slice::Iter<'_, A>> {
+        iter::once(&self.head).chain(self.tail.iter())
+    }
+
+    pub fn into_vec(self) -> Vec<A> {
+        let mut vec = Vec::with_capacity(self.len());
+        vec.push(self.head);
+        vec.extend(self.tail);
+        vec
+    }
+
+}
+
+impl<A> IntoIterator for NonEmpty<A> {
+    type Item = A;
+    type IntoIter = iter::Chain<iter::Once<A>, ::std::vec::IntoIter<A>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        iter::once(self.head).chain(self.tail)
+    }
+}
+
+impl<A> Param for NonEmpty<A> {
+    type Param = A;
+}
+
+impl<A, B> ReParam<B> for NonEmpty<A> {
+    type Output = NonEmpty<B>;
+}
+
+impl<'a, A, B> Covariant<'a, B> for NonEmpty<A> {
+    fn fmap<F: 'a + Fn(A) -> B>(self, f: F) -> NonEmpty<B> {
+        NonEmpty{head: f(self.head), tail: self.tail.into_iter().map(f).collect()}
+    }
+}
+
+impl<'a, A, B> CovariantRef<'a, B> for NonEmpty<A> {
+    fn fmap_ref<F: 'a + Fn(&'a A) -> B>(&'a self, f: F) -> NonEmpty<B> {
+        NonEmpty{head: f(&self.head), tail: self.tail.iter().map(f).collect()}
+    }
+}
+
+impl<'a, A, B> Traversable<'a, B> for NonEmpty<A> {
+    fn traverse<E, F: 'a + Fn(A) -> Result<B, E>>(self, f: F) -> Result<NonEmpty<B>, E> {
+        let head = f(self.head)?;
+        let tail = self.tail.into_iter().map(f).collect::<Result<_, _>>()?;
+        Ok(NonEmpty{head, tail})
+    }
+}
+
+impl<A> Foldable for NonEmpty<A> {
+    fn fold<B, F: Fn(B, A) -> B>(self, init: B, f: F) -> B {
+        self.into_iter().fold(init, f)
+    }
+}
+
+impl<A> Extract for NonEmpty<A> {
+    fn extract(self) -> A {
+        self.head
+    }
+}
+
+/// `extend` applies the function to every non-empty suffix, starting
+/// with the whole vector.
+impl<'a, A: Clone, B> Comonad<'a, B> for NonEmpty<A> {
+    fn extend<F: 'a + Fn(&NonEmpty<A>) -> B>(&self, f: F) -> NonEmpty<B> {
+        let tail = (0..self.tail.len()).map(|i| {
+            f(&NonEmpty{head: self.tail[i].clone(), tail: self.tail[i + 1..].to_vec()})
+        }).collect();
+        NonEmpty{head: f(self), tail}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NonEmpty;
+    use crate::Coyoneda;
+    use crate::functor::{Comonad, Covariant, Extract, Foldable};
+
+    #[test]
+    fn fmap_and_fold() {
+        let x = NonEmpty{head: 1, tail: vec![2, 3]};
+        let y = Coyoneda::from(x).fmap(|n: i32| n * 10).unwrap();
+        assert_eq!(y.fold(0, |a, b| a + b), 60)
+    }
+
+    #[test]
+    fn extend_suffixes() {
+        let x = NonEmpty{head: 1, tail: vec![2, 3]};
+        let y = x.extend(|s| s.clone().fold(0, |a, b| a + b));
+        assert_eq!(y, NonEmpty{head: 6, tail: vec![5, 3]});
+        assert_eq!(y.extract(), 6)
+    }
+
+    #[test]
+    fn from_vec() {
+        assert_eq!(NonEmpty::<i32>::from_vec(vec![]), None);
+        assert_eq!(NonEmpty::from_vec(vec![1, 2]).map(|x| x.into_vec()), Some(vec![1, 2]))
+    }
+}
@@ -0,0 +1,115 @@
+//! Fallible pipelines made of named stages.
+//!
+//! A `Pipeline` is a `TryMorphism` in which every closure is a named
+//! stage. When a stage fails, the error says which stage it was, along
+//! with the `Debug` representation of the input it failed on.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::morphism::{TryMorphism, Void};
+
+/// The error of a failed pipeline stage.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StageError<E> {
+    /// The name of the stage that failed.
+    pub stage: &'static str,
+    /// The position of the stage in the pipeline, counting from zero.
+    pub index: usize,
+    /// The input of the stage, formatted with `Debug`.
+    pub input: String,
+    /// The error returned by the stage.
+    pub error: E,
+}
+
+impl<E: fmt::Display> fmt::Display for StageError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "stage {} `{}` failed on {}: {}", self.index, self.stage, self.input, self.error)
+    }
+}
+
+impl<E: Error + 'static> Error for StageError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// A chain of named, fallible stages from `A` to `B`.
+///
+/// # Example
+///
+/// ```rust
+/// use coyoneda::pipeline::Pipeline;
+///
+/// let p = Pipeline::new::<&str, String>()
+///     .stage("trim", |s| Ok(s.trim()))
+///     .stage("parse", |s| s.parse::<u32>().map_err(|e| e.to_string()))
+///     .stage("halve", |x| if x % 2 == 0 { Ok(x / 2) } else { Err("odd".to_string()) });
+/// assert_eq!(p.run(" 84 "), Ok(42));
+/// let e = p.run("7").unwrap_err();
+/// assert_eq!((e.stage, e.input.as_str()), ("halve", "7"));
+/// assert_eq!(e.to_string(), "stage 2 `halve` failed on 7: odd");
+/// ```
+pub struct Pipeline<'a, A, B, E> {
+    chain: TryMorphism<'a, A, B, StageError<E>>,
+    stages: Vec<&'static str>,
+}
+
+impl Pipeline<'static, Void, Void, Void> {
+    /// Create a pipeline without any stages.
+    pub fn new<'a, A, E>() -> Pipeline<'a, A, A, E> {
+        Pipeline{chain: TryMorphism::new(), stages: Vec::new()}
+    }
+}
+
+impl<'a, A, B: 'a, E> Pipeline<'a, A, B, E> {
+    /// Add a stage called `name` at the end of the pipeline.
+    ///
+    /// The stage borrows its input, which is only formatted if the stage
+    /// fails.
+    pub fn stage<C: 'a, F>(mut self, name: &'static str, f: F) -> Pipeline<'a, A, C, E>
+        where B: fmt::Debug, F: Fn(&B) -> Result<C, E> + 'a,
+    {
+        let index = self.stages.len();
+        self.stages.push(name);
+        let chain = self.chain.tail(move |b: B| {
+            f(&b).map_err(|error| StageError {
+                stage: name,
+                index,
+                input: format!("{:?}", b),
+                error,
+            })
+        });
+        Pipeline{chain, stages: self.stages}
+    }
+
+    /// The names of the stages, in the order they run.
+    pub fn stages(&self) -> &[&'static str] {
+        &self.stages
+    }
+
+    /// Run the stages in a loop, stopping at the first one that fails.
+    pub fn run(&self, x: A) -> Result<B, StageError<E>> {
+        self.chain.run(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::ParseIntError;
+
+    use super::{Pipeline, StageError};
+
+    #[test]
+    fn report_failed_stage() {
+        let p = Pipeline::new::<Vec<&str>, ParseIntError>()
+            .stage("parse", |v| v.iter().map(|s| s.parse()).collect::<Result<Vec<i64>, _>>())
+            .stage("sum", |v| Ok(v.iter().sum::<i64>()));
+        assert_eq!(p.stages(), ["parse", "sum"]);
+        assert_eq!(p.run(vec!["1", "2"]), Ok(3));
+        let e: StageError<_> = p.run(vec!["1", "x"]).unwrap_err();
+        assert_eq!((e.stage, e.index), ("parse", 0));
+        assert_eq!(e.input, r#"["1", "x"]"#);
+        assert!(std::error::Error::source(&e).is_some());
+    }
+}
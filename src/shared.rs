@@ -0,0 +1,128 @@
+//! A `Coyoneda` that can be shared between threads.
+//!
+//! `SharedCoyoneda` keeps its point and its chain of closures behind
+//! `Arc`s. Cloning it is cheap, and every clone can be extended with
+//! further `fmap`s independently, so a common prefix can be built once
+//! and fanned out to several threads.
+
+use std::any::Any;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::functor::Covariant;
+use crate::functor::parametric::{Param, ReParam};
+use crate::morphism::Morphism;
+
+// Attaches one closure to the back of a `Morphism` that starts at the
+// parameter of the point. The morphism is passed in and out as `Any`,
+// because its type at the back differs from step to step, but this only
+// happens once per `unwrap`, and not for every value that is mapped.
+type Step = Arc<dyn Fn(Box<dyn Any>) -> Box<dyn Any> + Send + Sync>;
+
+/// A `Coyoneda` whose point and chain are reference-counted, and whose
+/// closures are required to be `Send + Sync`.
+///
+/// The chain is kept as a flat list of steps. Mapping over a value that
+/// isn't shared pushes onto the list in place, while mapping over one
+/// of several clones copies the list of pointers first.
+///
+/// On `unwrap`, the steps are assembled into a `Morphism`, which then
+/// runs on every value in the point without any further allocation.
+pub struct SharedCoyoneda<T: Param, B> {
+    point: Arc<T>,
+    steps: Arc<Vec<Step>>,
+    phan: PhantomData<fn(T::Param) -> B>
+}
+
+impl<T: Param, B> Clone for SharedCoyoneda<T, B> {
+    fn clone(&self) -> Self {
+        SharedCoyoneda{point: self.point.clone(), steps: self.steps.clone(), phan: PhantomData}
+    }
+}
+
+impl<T: Param, B: 'static> SharedCoyoneda<T, B> where T::Param: 'static {
+
+    pub fn fmap<C: 'static, F>(self, f: F) -> SharedCoyoneda<T, C>
+        where F: Fn(B) -> C + Send + Sync + 'static {
+        let f = Arc::new(f);
+        let step: Step = Arc::new(move |m: Box<dyn Any>| {
+            let f = f.clone();
+            match m.downcast::<Morphism<'static, T::Param, B>>() {
+                Ok(m) => Box::new(m.tail(move |b| f(b))),
+                Err(_) => unreachable!()
+            }
+        });
+        let mut steps = self.steps;
+        Arc::make_mut(&mut steps).push(step);
+        SharedCoyoneda{point: self.point, steps, phan: PhantomData}
+    }
+
+    /// Lower the suspension, cloning the point if it is still shared.
+    pub fn unwrap(self) -> <T as ReParam<B>>::Output
+        where T: Clone + Covariant<'static, B> {
+        let point = Arc::try_unwrap(self.point).unwrap_or_else(|p| T::clone(&p));
+        let mut id = Morphism::new::<T::Param>();
+        id.reserve(self.steps.len());
+        let mut morph: Box<dyn Any> = Box::new(id);
+        for step in self.steps.iter() {
+            morph = step(morph);
+        }
+        let morph = match morph.downcast::<Morphism<'static, T::Param, B>>() {
+            Ok(m) => m,
+            Err(_) => unreachable!()
+        };
+        T::fmap(point, move |a| morph.run(a))
+    }
+
+}
+
+impl<T: Param> From<T> for SharedCoyoneda<T, T::Param> {
+    fn from(x: T) -> SharedCoyoneda<T, T::Param> {
+        SharedCoyoneda{point: Arc::new(x), steps: Arc::new(Vec::new()), phan: PhantomData}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    use super::SharedCoyoneda;
+
+    #[test]
+    fn branch_across_threads() {
+        let prefix = SharedCoyoneda::from(Some(20)).fmap(|n: i32| n * 2);
+        let handles: Vec<_> = (0..4).map(|i| {
+            let branch = prefix.clone();
+            thread::spawn(move || branch.fmap(move |n| n + i).unwrap())
+        }).collect();
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(results, vec![Some(40), Some(41), Some(42), Some(43)]);
+        assert_eq!(prefix.fmap(|n| n.to_string()).unwrap(), Some("40".to_string()))
+    }
+
+    #[test]
+    fn unwrap_many_values() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counted = calls.clone();
+        let prefix = SharedCoyoneda::from((0..100).collect::<Vec<u64>>()).fmap(|n| n + 1);
+        let steps = prefix.steps.clone();
+        let x = prefix.clone().fmap(move |n| { counted.fetch_add(1, Ordering::Relaxed); n * 2 });
+        assert_eq!((x.steps.len(), steps.len()), (2, 1));
+        let ys = x.fmap(|n| n.to_string()).unwrap();
+        assert_eq!((ys.len(), ys[99].as_str()), (100, "200"));
+        assert_eq!(calls.load(Ordering::Relaxed), 100);
+        assert_eq!(prefix.unwrap()[0], 1)
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn long_chain() {
+        let mut x = SharedCoyoneda::from(Box::new(0u64));
+        for _ in 0..100000 {
+            x = x.fmap(|n| n + 1);
+        }
+        assert_eq!(x.unwrap(), Box::new(100000))
+    }
+}
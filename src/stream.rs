@@ -0,0 +1,66 @@
+//! A functor over streams, available with the `futures` feature.
+//!
+//! `MappedStream` wraps any `Stream`, so that per-item mapping can be
+//! suspended in `Coyoneda` and fused into a single step before polling
+//! starts.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::{Stream, StreamExt};
+
+use crate::functor::Covariant;
+use crate::functor::parametric::{Param, ReParam};
+
+pub struct MappedStream<'a, A> {
+    inner: Pin<Box<dyn Stream<Item=A> + 'a>>
+}
+
+impl<'a, A> MappedStream<'a, A> {
+    pub fn new<S: Stream<Item=A> + 'a>(stream: S) -> MappedStream<'a, A> {
+        MappedStream{inner: Box::pin(stream)}
+    }
+}
+
+impl<'a, A> Stream for MappedStream<'a, A> {
+    type Item = A;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<A>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, A> Param for MappedStream<'a, A> {
+    type Param = A;
+}
+
+impl<'a, A, B> ReParam<B> for MappedStream<'a, A> {
+    type Output = MappedStream<'a, B>;
+}
+
+impl<'b: 'a, 'a, A: 'a, B> Covariant<'b, B> for MappedStream<'a, A> {
+    fn fmap<F: 'b + Fn(A) -> B>(self, f: F) -> MappedStream<'a, B> {
+        MappedStream::new(self.inner.map(f))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+    use futures::{stream, StreamExt};
+
+    use super::MappedStream;
+    use crate::Coyoneda;
+    use crate::functor::Covariant;
+
+    #[test]
+    fn map_stream() {
+        let x = MappedStream::new(stream::iter(vec![1, 2, 3]));
+        let y = Coyoneda::from(x).fmap(|n: i32| n * 2).fmap(|n| n.to_string());
+        assert_eq!(block_on(y.unwrap().collect::<Vec<_>>()), vec!["2", "4", "6"])
+    }
+}
@@ -0,0 +1,125 @@
+//! A functor over thread handles.
+//!
+//! `MappedJoinHandle` wraps a `std::thread::JoinHandle`, so it can be
+//! lifted into `Coyoneda`. Lowering the suspension yields a handle whose
+//! `join` applies the accumulated chain to the thread's result.
+
+use std::thread::{self, JoinHandle, Thread};
+
+use crate::functor::{Covariant, CovariantOnce};
+use crate::functor::parametric::{Param, ReParam};
+
+trait Join<A> {
+    fn join(self: Box<Self>) -> thread::Result<A>;
+    fn thread(&self) -> &Thread;
+    fn is_finished(&self) -> bool;
+}
+
+impl<A> Join<A> for JoinHandle<A> {
+    fn join(self: Box<Self>) -> thread::Result<A> {
+        JoinHandle::join(*self)
+    }
+
+    fn thread(&self) -> &Thread {
+        JoinHandle::thread(self)
+    }
+
+    fn is_finished(&self) -> bool {
+        JoinHandle::is_finished(self)
+    }
+}
+
+struct Map<'a, A, F> {
+    inner: Box<dyn Join<A> + 'a>,
+    f: F
+}
+
+impl<'a, A, B, F: FnOnce(A) -> B> Join<B> for Map<'a, A, F> {
+    fn join(self: Box<Self>) -> thread::Result<B> {
+        let this = *self;
+        this.inner.join().map(this.f)
+    }
+
+    fn thread(&self) -> &Thread {
+        self.inner.thread()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+}
+
+pub struct MappedJoinHandle<'a, A> {
+    inner: Box<dyn Join<A> + 'a>
+}
+
+impl<'a, A: 'a> MappedJoinHandle<'a, A> {
+    pub fn new(handle: JoinHandle<A>) -> MappedJoinHandle<'a, A> {
+        MappedJoinHandle{inner: Box::new(handle)}
+    }
+}
+
+impl<'a, A> MappedJoinHandle<'a, A> {
+    /// Wait for the thread to finish, and return its result with the
+    /// chain applied. The chain is not run if the thread panicked.
+    pub fn join(self) -> thread::Result<A> {
+        self.inner.join()
+    }
+
+    pub fn thread(&self) -> &Thread {
+        self.inner.thread()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+}
+
+impl<'a, A: 'a> From<JoinHandle<A>> for MappedJoinHandle<'a, A> {
+    fn from(handle: JoinHandle<A>) -> MappedJoinHandle<'a, A> {
+        MappedJoinHandle::new(handle)
+    }
+}
+
+impl<'a, A> Param for MappedJoinHandle<'a, A> {
+    type Param = A;
+}
+
+impl<'a, A, B> ReParam<B> for MappedJoinHandle<'a, A> {
+    type Output = MappedJoinHandle<'a, B>;
+}
+
+impl<'b: 'a, 'a, A: 'a, B> Covariant<'b, B> for MappedJoinHandle<'a, A> {
+    fn fmap<F: 'b + Fn(A) -> B>(self, f: F) -> MappedJoinHandle<'a, B> {
+        self.fmap_once(f)
+    }
+}
+
+impl<'b: 'a, 'a, A: 'a, B> CovariantOnce<'b, B> for MappedJoinHandle<'a, A> {
+    fn fmap_once<F: 'b + FnOnce(A) -> B>(self, f: F) -> MappedJoinHandle<'a, B> {
+        MappedJoinHandle{inner: Box::new(Map{inner: self.inner, f})}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::MappedJoinHandle;
+    use crate::Coyoneda;
+    use crate::functor::Covariant;
+
+    #[test]
+    fn map_join_handle() {
+        let x = MappedJoinHandle::new(thread::spawn(|| 20));
+        let y = Coyoneda::from(x).fmap(|n: i32| n * 2).fmap(|n| n.to_string());
+        assert_eq!(y.unwrap().join().unwrap(), "40")
+    }
+
+    #[test]
+    fn join_panicked() {
+        let x = MappedJoinHandle::new(thread::spawn(|| -> i32 { panic!("boom") }));
+        let y = Coyoneda::from(x).fmap(|_: i32| -> i32 { unreachable!() });
+        assert!(y.unwrap().join().is_err())
+    }
+}
@@ -0,0 +1,126 @@
+//! Call-by-need values.
+//!
+//! A `Thunk` is a suspended computation producing an `A`. Mapping over
+//! it extends a `Morphism` rather than nesting closures, so arbitrarily
+//! long chains can be built and forced without growing the stack. The
+//! computation runs at most once; its result is cached by `force`.
+
+use std::cell::{Cell, OnceCell};
+
+use crate::morphism::{Morphism, Outlives};
+use crate::functor::{Covariant, Monad, Pure};
+use crate::functor::parametric::{Param, ReParam};
+
+pub struct Thunk<'a, A> {
+    morph: Morphism<'a, (), A>,
+    value: OnceCell<A>
+}
+
+impl<'a, A: 'a> Thunk<'a, A> {
+    /// Suspend the computation `f` until the thunk is forced.
+    pub fn new<F: 'a + Fn() -> A>(f: F) -> Thunk<'a, A> {
+        Thunk{morph: Morphism::new().tail(move |()| f()), value: OnceCell::new()}
+    }
+
+    /// A thunk that has already been evaluated to `x`.
+    pub fn from_value(x: A) -> Thunk<'a, A> {
+        Thunk{morph: Self::take_once(x), value: OnceCell::new()}
+    }
+
+    // The morphism of a thunk is run at most once, so it may move `x`
+    // out of its environment.
+    fn take_once(x: A) -> Morphism<'a, (), A> {
+        let slot = Cell::new(Some(x));
+        Morphism::new().tail(move |()| match slot.take() {
+            Some(x) => x,
+            None => unreachable!()
+        })
+    }
+}
+
+impl<'a, A> Thunk<'a, A> {
+    /// Run the computation if it hasn't been run yet, and borrow its result.
+    pub fn force(&self) -> &A {
+        self.value.get_or_init(|| self.morph.run(()))
+    }
+
+    /// Run the computation if it hasn't been run yet, and take its result.
+    pub fn into_inner(self) -> A {
+        let morph = self.morph;
+        self.value.into_inner().unwrap_or_else(|| morph.run(()))
+    }
+
+    /// Whether the computation has already been run.
+    pub fn is_forced(&self) -> bool {
+        self.value.get().is_some()
+    }
+}
+
+impl<'a, A> Param for Thunk<'a, A> {
+    type Param = A;
+}
+
+impl<'a, A, B> ReParam<B> for Thunk<'a, A> {
+    type Output = Thunk<'a, B>;
+}
+
+impl<'b: 'a, 'a, A: 'a, B: Outlives<'a>> Covariant<'b, B> for Thunk<'a, A> {
+    fn fmap<F: 'b + Fn(A) -> B>(self, f: F) -> Thunk<'a, B> {
+        let morph = match self.value.into_inner() {
+            Some(x) => Thunk::take_once(x).tail(f),
+            None => self.morph.tail(f)
+        };
+        Thunk{morph, value: OnceCell::new()}
+    }
+}
+
+impl<'b: 'a, 'a, A: 'a, B: Outlives<'a>> Monad<'b, B> for Thunk<'a, A> {
+    fn bind<F: 'b + Fn(A) -> Thunk<'a, B>>(self, f: F) -> Thunk<'a, B> {
+        self.fmap(move |x| f(x).into_inner())
+    }
+}
+
+impl<'a, A: 'a> Pure for Thunk<'a, A> {
+    fn pure(x: A) -> Thunk<'a, A> {
+        Thunk::from_value(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::Thunk;
+    use crate::Coyoneda;
+    use crate::functor::{Covariant, Monad};
+
+    #[test]
+    fn force_once() {
+        let runs = Cell::new(0);
+        let x = Thunk::new(|| { runs.set(runs.get() + 1); 20 }).fmap(|n: i32| n * 2);
+        assert_eq!(runs.get(), 0);
+        assert_eq!(*x.force(), 40);
+        assert_eq!(*x.force(), 40);
+        assert_eq!(runs.get(), 1);
+        assert_eq!(x.fmap(|n| n + 2).into_inner(), 42);
+        assert_eq!(runs.get(), 1)
+    }
+
+    #[test]
+    fn bind_lazily() {
+        let x = Thunk::from_value(20).bind(|n| Thunk::new(move || n + 1));
+        let y = Coyoneda::from(x).fmap(|n: i32| n.to_string()).unwrap();
+        assert!(!y.is_forced());
+        assert_eq!(y.into_inner(), "21")
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn long_chain() {
+        let mut x = Thunk::new(|| 0u64);
+        for _ in 0..100000 {
+            x = x.fmap(|n| n + 1);
+        }
+        assert_eq!(x.into_inner(), 100000)
+    }
+}
@@ -0,0 +1,109 @@
+//! Optional `tracing` instrumentation of suspended pipelines.
+//!
+//! With the `tracing` feature enabled, every step of a chain runs inside
+//! a `fmap` span, and natural transformations run inside a `transform`
+//! span. Without it, these helpers compile down to nothing.
+
+#[cfg(feature = "tracing")]
+pub fn step<'a, B, C, F>(name: Option<&'static str>, f: F) -> impl Fn(B) -> C + 'a
+    where F: 'a + Fn(B) -> C {
+    move |b| {
+        let span = tracing::trace_span!("fmap", name = name);
+        let _guard = span.enter();
+        f(b)
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+pub fn step<'a, B, C, F>(_name: Option<&'static str>, f: F) -> F
+    where F: 'a + Fn(B) -> C {
+    f
+}
+
+#[cfg(feature = "tracing")]
+pub fn transform<R, F: FnOnce() -> R>(f: F) -> R {
+    let span = tracing::trace_span!("transform");
+    let _guard = span.enter();
+    f()
+}
+
+#[cfg(not(feature = "tracing"))]
+pub fn transform<R, F: FnOnce() -> R>(f: F) -> R {
+    f()
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    use crate::Coyoneda;
+    use crate::functor::Covariant;
+
+    // Records the name of every span entered, along with its `name`
+    // field if it has one.
+    #[derive(Clone, Default)]
+    struct Spans {
+        spans: Arc<Mutex<Vec<String>>>,
+        entered: Arc<Mutex<Vec<String>>>,
+    }
+
+    struct Name<'s>(&'s mut String);
+
+    impl<'s> Visit for Name<'s> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "name" {
+                self.0.push(':');
+                self.0.push_str(value);
+            }
+        }
+
+        fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {}
+    }
+
+    impl Subscriber for Spans {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+            let mut name = attrs.metadata().name().to_string();
+            attrs.record(&mut Name(&mut name));
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(name);
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, id: &Id) {
+            let name = self.spans.lock().unwrap()[id.into_u64() as usize - 1].clone();
+            self.entered.lock().unwrap().push(name);
+        }
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn spans() {
+        let subscriber = Spans::default();
+        let entered = subscriber.entered.clone();
+        let y = tracing::subscriber::with_default(subscriber, || {
+            Coyoneda::from(Some(41))
+                .fmap_named("inc", |n: i32| n + 1)
+                .fmap(|n: i32| n.to_string())
+                .natural_transform_to::<Vec<_>>()
+                .unwrap()
+        });
+        assert_eq!(y, ["42"]);
+        assert_eq!(*entered.lock().unwrap(), ["transform", "fmap:inc", "fmap"]);
+    }
+}
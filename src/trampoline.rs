@@ -0,0 +1,235 @@
+//! Stack-safe recursion.
+//!
+//! A `Trampoline` is a computation that is either done, or has more
+//! work to do. Recursive functions return one instead of calling
+//! themselves directly, and `run` then evaluates it in a loop, keeping
+//! any pending continuations on the heap. Like the steps of a
+//! `Morphism`, continuations hand their values on through shared cells,
+//! so neither deep recursion nor long chains of `bind` grow the stack.
+
+use std::cell::Cell;
+use std::mem;
+use std::rc::Rc;
+
+use crate::functor::{Covariant, Monad, Pure};
+use crate::functor::parametric::{Param, ReParam};
+
+type Point<A> = Rc<Cell<Option<A>>>;
+
+// A unit of work for the evaluation loop, which may schedule more.
+type Task<'a> = Box<dyn FnOnce(&mut Tasks<'a>) + 'a>;
+
+// The pending work of the evaluation loop, run last in, first out.
+struct Tasks<'a>(Vec<Task<'a>>);
+
+pub struct Trampoline<'a, A> {
+    state: State<'a, A>
+}
+
+enum State<'a, A> {
+    Done(A),
+    More(Box<dyn FnOnce() -> Trampoline<'a, A> + 'a>),
+    Bind(Box<dyn Schedule<'a, A> + 'a>),
+    // Left behind once the state has been moved out.
+    Taken,
+}
+
+// A trampoline bound to a continuation, with the type in between erased.
+trait Schedule<'a, A>: Dismantle<'a> {
+    fn schedule(self: Box<Self>, out: Point<A>, tasks: &mut Tasks<'a>);
+}
+
+// Binds nest as deeply as they are chained, so dropping them has to be
+// done in a loop as well. Instead of dropping the trampolines it holds,
+// a value passes them on to `rest`.
+trait Dismantle<'a> {
+    fn dismantle(self: Box<Self>, rest: &mut Vec<Box<dyn Dismantle<'a> + 'a>>);
+}
+
+impl<'a, A> Drop for Trampoline<'a, A> {
+    fn drop(&mut self) {
+        if let State::Bind(b) = mem::replace(&mut self.state, State::Taken) {
+            let mut rest = Vec::new();
+            b.dismantle(&mut rest);
+            while let Some(t) = rest.pop() {
+                t.dismantle(&mut rest);
+            }
+        }
+    }
+}
+
+impl<'a, A> Dismantle<'a> for Trampoline<'a, A> {
+    fn dismantle(mut self: Box<Self>, rest: &mut Vec<Box<dyn Dismantle<'a> + 'a>>) {
+        if let State::Bind(b) = mem::replace(&mut self.state, State::Taken) {
+            b.dismantle(rest);
+        }
+    }
+}
+
+struct Bound<'a, X, F> {
+    first: Trampoline<'a, X>,
+    f: F,
+}
+
+impl<'a, X: 'a, F> Dismantle<'a> for Bound<'a, X, F> {
+    fn dismantle(self: Box<Self>, rest: &mut Vec<Box<dyn Dismantle<'a> + 'a>>) {
+        rest.push(Box::new(self.first));
+    }
+}
+
+impl<'a, X: 'a, A: 'a, F> Schedule<'a, A> for Bound<'a, X, F>
+    where F: FnOnce(X) -> Trampoline<'a, A> + 'a,
+{
+    fn schedule(self: Box<Self>, out: Point<A>, tasks: &mut Tasks<'a>) {
+        let point = Rc::new(Cell::new(None));
+        let (mid, f) = (point.clone(), self.f);
+        // Push `first` last, so that it is evaluated before its result is
+        // passed on to `f`.
+        tasks.0.push(Box::new(move |tasks: &mut Tasks<'a>| match mid.take() {
+            Some(x) => tasks.0.push(f(x).eval(out)),
+            None => unreachable!()
+        }));
+        tasks.0.push(self.first.eval(point));
+    }
+}
+
+impl<'a, A: 'a> Trampoline<'a, A> {
+    /// A computation that is already done.
+    pub fn done(x: A) -> Trampoline<'a, A> {
+        Trampoline{state: State::Done(x)}
+    }
+
+    /// A computation that continues with `f`.
+    pub fn more<F: FnOnce() -> Trampoline<'a, A> + 'a>(f: F) -> Trampoline<'a, A> {
+        Trampoline{state: State::More(Box::new(f))}
+    }
+
+    /// Continue with `f` once this computation is done.
+    pub fn and_then<B: 'a, F>(self, f: F) -> Trampoline<'a, B>
+        where F: FnOnce(A) -> Trampoline<'a, B> + 'a,
+    {
+        Trampoline{state: State::Bind(Box::new(Bound{first: self, f}))}
+    }
+
+    /// Evaluate the computation in a loop.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use coyoneda::trampoline::Trampoline;
+    ///
+    /// fn sum(n: u64) -> Trampoline<'static, u64> {
+    ///     if n == 0 {
+    ///         return Trampoline::done(0);
+    ///     }
+    ///     Trampoline::more(move || sum(n - 1)).and_then(move |s| Trampoline::done(s + n))
+    /// }
+    ///
+    /// assert_eq!(sum(1000000).run(), 500000500000);
+    /// ```
+    pub fn run(self) -> A {
+        let out = Rc::new(Cell::new(None));
+        let mut tasks = Tasks(vec![self.eval(out.clone())]);
+        while let Some(task) = tasks.0.pop() {
+            task(&mut tasks);
+        }
+        match out.take() {
+            Some(x) => x,
+            None => unreachable!()
+        }
+    }
+
+    // The task of evaluating this computation into `out`.
+    fn eval(mut self, out: Point<A>) -> Task<'a> {
+        let state = mem::replace(&mut self.state, State::Taken);
+        Box::new(move |tasks: &mut Tasks<'a>| match state {
+            State::Done(x) => out.set(Some(x)),
+            State::More(f) => tasks.0.push(f().eval(out)),
+            State::Bind(b) => b.schedule(out, tasks),
+            State::Taken => unreachable!(),
+        })
+    }
+}
+
+impl<'a, A> Param for Trampoline<'a, A> {
+    type Param = A;
+}
+
+impl<'a, A, B> ReParam<B> for Trampoline<'a, A> {
+    type Output = Trampoline<'a, B>;
+}
+
+impl<'b: 'a, 'a, A: 'a, B: 'a> Covariant<'b, B> for Trampoline<'a, A> {
+    fn fmap<F: 'b + Fn(A) -> B>(self, f: F) -> Trampoline<'a, B> {
+        self.and_then(move |x| Trampoline::done(f(x)))
+    }
+}
+
+impl<'b: 'a, 'a, A: 'a, B: 'a> Monad<'b, B> for Trampoline<'a, A> {
+    fn bind<F: 'b + Fn(A) -> Trampoline<'a, B>>(self, f: F) -> Trampoline<'a, B> {
+        self.and_then(f)
+    }
+}
+
+impl<'a, A: 'a> Pure for Trampoline<'a, A> {
+    fn pure(x: A) -> Trampoline<'a, A> {
+        Trampoline::done(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Trampoline;
+    use crate::Coyoneda;
+    use crate::functor::{Covariant, Monad};
+
+    fn even(n: u64) -> Trampoline<'static, bool> {
+        if n == 0 { Trampoline::done(true) } else { Trampoline::more(move || odd(n - 1)) }
+    }
+
+    fn odd(n: u64) -> Trampoline<'static, bool> {
+        if n == 0 { Trampoline::done(false) } else { Trampoline::more(move || even(n - 1)) }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn mutual_recursion() {
+        assert!(even(100000).run());
+        assert!(odd(100001).run());
+    }
+
+    #[test]
+    fn map_and_bind() {
+        let x = Trampoline::done(20).bind(|n| Trampoline::more(move || Trampoline::done(n + 1)));
+        let y = Coyoneda::from(x).fmap(|n: i32| n * 2).unwrap();
+        assert_eq!(y.run(), 42)
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn deep_binds() {
+        fn depth(n: u64) -> Trampoline<'static, u64> {
+            if n == 0 {
+                return Trampoline::done(0);
+            }
+            Trampoline::more(move || depth(n - 1)).and_then(|d| Trampoline::done(d + 1))
+        }
+        assert_eq!(depth(200000).run(), 200000);
+
+        let mut x = Trampoline::done(0u64);
+        for _ in 0..200000 {
+            x = x.fmap(|n| n + 1);
+        }
+        assert_eq!(x.run(), 200000)
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn drop_deep_binds() {
+        let mut x = Trampoline::done(0u64);
+        for _ in 0..1000000 {
+            x = x.fmap(|n| n + 1);
+        }
+        drop(x)
+    }
+}